@@ -31,6 +31,10 @@ fn get_sys_uni_list_path() -> String {
     "../cfg/".to_owned() + "syscall_list"
 }
 
+fn get_sys_uni_class_path() -> String {
+    "../cfg/".to_owned() + "syscall_class"
+}
+
 fn get_default_header_dir_path() -> String {
     "../cfg/".to_owned() + "header"
 }
@@ -130,6 +134,16 @@ fn make_nr_inc(src: &str, dst: &str) {
     }
     w.write(b"_ => super::NR::sys_unknown,\n").unwrap();
     w.write(b"}\n}\n").unwrap();
+    w.write(b"\n").unwrap();
+    w.write(b"/// Get raw syscall number from common syscall enum, None if not exist on this architecture\n").unwrap();
+    w.write(b"pub fn from_uni(sys: super::NR) -> Option<u64> {\nmatch sys {\n").unwrap();
+    for line in BufReader::new(std::fs::File::open(src).unwrap()).lines() {
+        let mut l = line.as_ref().unwrap().split(':');
+        let name = l.next().unwrap();
+        write!(w, "super::NR::sys_{} => Some(sys_{}),\n", name, name).unwrap();
+    }
+    w.write(b"_ => None,\n").unwrap();
+    w.write(b"}\n}\n").unwrap();
     println!("cargo:rerun-if-changed={}", src);
 }
 
@@ -165,6 +179,51 @@ fn make_uni_map_inc(dst: &str, set: &BTreeSet<String>) {
     w.write(b"];\n").unwrap();
 }
 
+/// Syscall class names, index is bit position of class bits
+const SYS_CLASS: [&str; 7] = ["file", "desc", "network", "process", "memory", "signal", "ipc"];
+
+fn load_sys_uni_class(src: &str, set: &BTreeSet<String>, btm: &mut BTreeMap<String, u32>) {
+    for line in BufReader::new(std::fs::File::open(src).unwrap()).lines() {
+        let mut l = line.as_ref().unwrap().split(':');
+        let name = l.next().unwrap();
+        if !set.contains(name) {
+            panic!("unknown syscall {} in {}", name, src);
+        }
+        let bits = l.next().unwrap().split(',').fold(0, |bits, c| {
+            match SYS_CLASS.iter().position(|x| *x == c) {
+                Some(i) => bits | (1 << i),
+                None => panic!("unknown syscall class {} in {}", c, src),
+            }
+        });
+        btm.insert(name.to_owned(), bits);
+    }
+    println!("cargo:rerun-if-changed={}", src);
+}
+
+fn make_uni_class_inc(dst: &str, btm: &BTreeMap<String, u32>) {
+    let mut w = BufWriter::new(std::fs::File::create(dst).unwrap());
+    w.write(b"/// Syscall class bits\n").unwrap();
+    w.write(b"pub mod class {\n").unwrap();
+    SYS_CLASS.iter().enumerate().for_each(|(i, x)|{
+        write!(w, "pub const {}: u32 = 0x{:x};\n", x.to_uppercase(), 1 << i).unwrap();
+    });
+    w.write(b"}\n").unwrap();
+    w.write(b"/// Map of syscall class name and bits, for lookup syscalls by class name\n").unwrap();
+    write!(w, "pub const class_map: [(&'static str, u32);{}] = [\n", SYS_CLASS.len()).unwrap();
+    SYS_CLASS.iter().for_each(|x|{
+        write!(w, "(\"{}\", class::{}),\n", x, x.to_uppercase()).unwrap();
+    });
+    w.write(b"];\n").unwrap();
+    w.write(b"impl NR {\n").unwrap();
+    w.write(b"/// Get class bits of syscall\n").unwrap();
+    w.write(b"pub fn class(&self) -> u32 {\nmatch self {\n").unwrap();
+    btm.iter().for_each(|(k, v)|{
+        write!(w, "NR::sys_{} => 0x{:x},\n", k, v).unwrap();
+    });
+    w.write(b"_ => 0,\n").unwrap();
+    w.write(b"}\n}\n}\n").unwrap();
+}
+
 fn make_uni_nr_rs(dst: &str, set: &BTreeSet<String>) {
     let enum_inc = dst.to_owned() + ".enum.inc";
     make_uni_enum_inc(&enum_inc, set);
@@ -172,17 +231,23 @@ fn make_uni_nr_rs(dst: &str, set: &BTreeSet<String>) {
     make_uni_to_str_inc(&to_str_inc, set);
     let to_str_inc = dst.to_owned() + ".map.inc";
     make_uni_map_inc(&to_str_inc, set);
+    let class_inc = dst.to_owned() + ".class.inc";
+    let mut btm = BTreeMap::new();
+    load_sys_uni_class(&get_sys_uni_class_path(), set, &mut btm);
+    make_uni_class_inc(&class_inc, &btm);
 }
 
 fn make_sys_rs (dir: &str) {
     let (a64, a32) = get_nr_file_path();
     let aun = get_sys_uni_list_path();
+    let acl = get_sys_uni_class_path();
     let (r64, r32, run) = get_nr_rs_file_path(dir);
 
 
     let w64 = ["build.rs", &a64].iter().any(|x|file_modified_than_file(x, &r64));
     let w32 = ["build.rs", &a32].iter().any(|x|file_modified_than_file(x, &r32));
-    let wun = w64 || w32;
+    let wun = w64 || w32 || file_modified_than_file(&acl, &(run.clone() + ".class.inc"));
+    println!("cargo:rerun-if-changed={}", acl);
     if w64 {
         make_nr_inc(&a64, &r64);
     }
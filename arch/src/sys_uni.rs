@@ -5,6 +5,7 @@
 include!(concat!(env!("OUT_DIR"), "/sys_uni.rs.enum.inc"));
 include!(concat!(env!("OUT_DIR"), "/sys_uni.rs.to_str.inc"));
 include!(concat!(env!("OUT_DIR"), "/sys_uni.rs.map.inc"));
+include!(concat!(env!("OUT_DIR"), "/sys_uni.rs.class.inc"));
 pub mod a64 {
 include!(concat!(env!("OUT_DIR"), "/sys_64.inc"));
 }
@@ -0,0 +1,123 @@
+//! Credentials, environment and working directory applied to executed command
+use std::ffi::CString;
+use std::io::{Result, Error, ErrorKind};
+
+mod c {
+    extern "C" {
+        pub fn getpwnam(name: *const types::SChar) -> *const passwd;
+        pub fn getpwuid(uid: types::UInt) -> *const passwd;
+        pub fn initgroups(user: *const types::SChar, group: types::UInt) -> types::SInt;
+        pub fn setgid(gid: types::UInt) -> types::SInt;
+        pub fn setuid(uid: types::UInt) -> types::SInt;
+    }
+
+    #[repr(C)]#[allow(non_camel_case_types)]
+    pub struct passwd {
+        pub pw_name: *const types::SChar,
+        pub pw_passwd: *const types::SChar,
+        pub pw_uid: types::UInt,
+        pub pw_gid: types::UInt,
+        pub pw_gecos: *const types::SChar,
+        pub pw_dir: *const types::SChar,
+        pub pw_shell: *const types::SChar,
+    }
+}
+
+fn check(r: types::SInt) -> Result<()> {
+    match r {
+        -1 => Err(Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+struct User {
+    name: CString,
+    uid: types::UInt,
+    gid: types::UInt,
+}
+
+/// Settings applied in forked child before the command is executed
+pub struct ExecEnv {
+    user: Option<User>,
+    /// Variables to set, or to unset if value is None
+    env: Vec<(String, Option<String>)>,
+    cwd: Option<String>,
+}
+
+impl ExecEnv {
+    pub fn new() -> Self {
+        ExecEnv{ user: None, env: vec![], cwd: None }
+    }
+
+    /// Run command as the user, with its primary and supplementary groups
+    /// # Arguments
+    /// * `user` - A user name or numeric user ID, which shall be in passwd database
+    pub fn set_user(&mut self, user: &str) -> Result<()> {
+        let name = CString::new(user).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        let mut pw = unsafe { c::getpwnam(name.as_ptr()) };
+        if pw.is_null() {
+            if let Ok(id) = user.parse::<types::UInt>() {
+                pw = unsafe { c::getpwuid(id) };
+            }
+        }
+        if pw.is_null() {
+            return Err(Error::new(ErrorKind::NotFound, format!("unknown user: {}", user)));
+        }
+        let pw = unsafe { &*pw };
+        let name = unsafe { std::ffi::CStr::from_ptr(pw.pw_name) }.to_owned();
+        self.user = Some(User{ name, uid: pw.pw_uid, gid: pw.pw_gid });
+        Ok(())
+    }
+
+    /// Set environment variable by `VAR=val`, or unset by `VAR`
+    pub fn set_env(&mut self, var: &str) {
+        match var.split_once('=') {
+            Some((k, v)) => self.env.push((k.to_string(), Some(v.to_string()))),
+            None => self.env.push((var.to_string(), None)),
+        }
+    }
+
+    /// Set working directory, it is changed after switching user
+    pub fn set_cwd(&mut self, dir: &str) {
+        self.cwd = Some(dir.to_string());
+    }
+
+    fn set_credentials(user: &User) -> Result<()> {
+        check(unsafe { c::initgroups(user.name.as_ptr(), user.gid) })?;
+        check(unsafe { c::setgid(user.gid) })?;
+        check(unsafe { c::setuid(user.uid) })
+    }
+
+    /// Apply settings to the calling process
+    pub fn apply(&self) -> Result<()> {
+        for (k, v) in &self.env {
+            match v {
+                Some(v) => std::env::set_var(k, v),
+                None => std::env::remove_var(k),
+            }
+        }
+        if let Some(user) = &self.user {
+            Self::set_credentials(user)?;
+        }
+        if let Some(dir) = &self.cwd {
+            std::env::set_current_dir(dir).map_err(|e| Error::new(e.kind(), format!("{}: {}", dir, e)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user() {
+        let mut env = ExecEnv::new();
+        assert!(env.set_user("root").is_ok());
+        assert!(env.user.as_ref().is_some_and(|u| u.uid == 0 && u.gid == 0));
+        assert!(env.set_user("0").is_ok());
+        assert!(env.user.as_ref().is_some_and(|u| u.name.as_bytes() == b"root"));
+        assert_eq!(env.set_user("4000000000").unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(env.set_user("no-such-user").unwrap_err().kind(), ErrorKind::NotFound);
+    }
+}
@@ -3,6 +3,15 @@ use std::os::unix::process::CommandExt;
 use std::process::Command;
 use std::io::{Result, Error, ErrorKind};
 use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+mod seccomp;
+pub use seccomp::SeccompFilter;
+mod exec_env;
+pub use exec_env::ExecEnv;
+mod record;
+pub use record::{BLOB, Blob, start_record, take_recorded, start_replay, add_replayed};
 
 #[allow(unused_macros)]
 macro_rules! LINE { () => { eprintln!("{}", line!()) } }
@@ -13,9 +22,26 @@ mod c {
         pub fn ptrace(request: types::SInt, pid: types::Pid, addr: *mut types::Void, data: *mut types::Void) -> types::SLong;
         pub fn getpid() -> types::Pid;
         pub fn fork() -> types::Pid;
+        pub fn _exit(status: types::SInt) -> !;
         pub fn kill(pid: types::Pid, sig: types::SInt) -> types::SInt;
         pub fn process_vm_readv(pid: types::Pid, dst: *const iovec, dstcnt: types::ULong,
                                 src: *const iovec, srccnt: types::ULong, flags: types::UInt) -> types::SSizeT;
+        pub fn process_vm_writev(pid: types::Pid, src: *const iovec, srccnt: types::ULong,
+                                 dst: *const iovec, dstcnt: types::ULong, flags: types::UInt) -> types::SSizeT;
+        pub fn __errno_location() -> *mut types::SInt;
+        pub fn sigemptyset(set: *mut sigset_t) -> types::SInt;
+        pub fn sigaddset(set: *mut sigset_t, sig: types::SInt) -> types::SInt;
+        pub fn pthread_sigmask(how: types::SInt, set: *const sigset_t, old: *mut sigset_t) -> types::SInt;
+        pub fn sigtimedwait(set: *const sigset_t, info: *mut types::Void, timeout: *const timespec) -> types::SInt;
+    }
+
+    #[allow(non_camel_case_types)]
+    pub type sigset_t = [types::ULong; 1024 / (8 * std::mem::size_of::<types::ULong>())];
+
+    #[repr(C)]#[allow(non_camel_case_types)]
+    pub struct timespec {
+        pub tv_sec: types::SLong,
+        pub tv_nsec: types::SLong,
     }
 
     #[repr(C)]#[allow(non_camel_case_types)]
@@ -24,8 +50,14 @@ mod c {
         pub iov_len: types::USizeT,
     }
 
+    pub const EINTR: types::SInt = 4;
     pub const ECHILD: types::SInt = 10;
+    pub const EAGAIN: types::SInt = 11;
+    pub const EFAULT: types::SInt = 14;
     pub const SIGTRAP: types::SInt = 5;
+    pub const SIGCHLD: types::SInt = 17;
+    pub const SIG_BLOCK: types::SInt = 0;
+    pub const SIG_SETMASK: types::SInt = 2;
     pub const SIGCONT: types::SInt = 18;
     pub const SIGSTOP: types::SInt = 19;
     pub const SIGTSTP: types::SInt = 20;
@@ -88,12 +120,52 @@ impl ptrace_syscall_info {
 }
 
 fn ptrace(request: types::SInt, pid: types::Pid, addr: *mut types::Void, data: *mut types::Void) -> Result<()> {
+    // no tracee to restart in replay, recorded events are output by the same calls as trace
+    if record::is_replay() {
+        return Ok(());
+    }
     match unsafe { c::ptrace(request, pid, addr, data) } {
         -1 => Err(Error::last_os_error()),
         _ => Ok(()),
     }
 }
 
+/// Set when tracees stop by seccomp filter instead of every syscall-enter
+static SECCOMP_MODE: AtomicBool = AtomicBool::new(false);
+
+fn is_seccomp_mode() -> bool {
+    SECCOMP_MODE.load(Ordering::Relaxed)
+}
+
+/// Descendants traced automatically
+#[derive(Copy, Clone, PartialEq)]
+pub enum FOLLOW {
+    /// Processes and threads
+    ALL,
+    /// Only threads, processes created by fork/vfork/clone with SIGCHLD are not traced
+    THREAD,
+    /// No descendants
+    NONE,
+}
+
+/// Descendants traced automatically, as `FOLLOW` value
+static FOLLOW_MODE: AtomicU8 = AtomicU8::new(FOLLOW::ALL as u8);
+
+/// Set descendants traced automatically, call before start trace
+/// # Arguments
+/// * `follow` - Descendants traced
+pub fn set_follow(follow: FOLLOW) {
+    FOLLOW_MODE.store(follow as u8, Ordering::Relaxed);
+}
+
+fn follow_option() -> types::SInt {
+    match FOLLOW_MODE.load(Ordering::Relaxed) {
+        x if x == FOLLOW::ALL as u8 => c::PTRACE_O_TRACEFORK | c::PTRACE_O_TRACEVFORK | c::PTRACE_O_TRACECLONE,
+        x if x == FOLLOW::THREAD as u8 => c::PTRACE_O_TRACECLONE,
+        _ => 0,
+    }
+}
+
 fn getpid() -> types::Pid {
     unsafe { c::getpid() }
 }
@@ -133,10 +205,8 @@ macro_rules! null_ptr {
     };
 }
 
-fn wait_one_stop_no_status(pid: types::Pid) -> Result<()> {
-    waiter::wait_one_stop(pid)?;
-    Ok(())
-}
+mod regs;
+pub use regs::{skip_syscall, set_return_value, replace_str_args};
 
 fn ptrace2(request: types::SInt, pid: types::Pid) -> Result<()> {
     ptrace(request, pid, NULL!(), NULL!())
@@ -146,10 +216,18 @@ fn ptrace_syscall(pid: types::Pid) -> Result<()> {
     ptrace2(c::PTRACE_SYSCALL, pid)
 }
 
+fn ptrace_cont(pid: types::Pid) -> Result<()> {
+    ptrace2(c::PTRACE_CONT, pid)
+}
+
 fn ptrace_syscall_sig(pid: types::Pid, sig: types::SInt) -> Result<()> {
     ptrace(c::PTRACE_SYSCALL, pid, NULL!(), void_ptr!(sig))
 }
 
+fn ptrace_cont_sig(pid: types::Pid, sig: types::SInt) -> Result<()> {
+    ptrace(c::PTRACE_CONT, pid, NULL!(), void_ptr!(sig))
+}
+
 fn ptrace_listen(pid: types::Pid) -> Result<()> {
     ptrace2(c::PTRACE_LISTEN, pid)
 }
@@ -158,16 +236,35 @@ fn ptrace_interrupt(pid: types::Pid) -> Result<()> {
     ptrace2(c::PTRACE_INTERRUPT, pid)
 }
 
+fn ptrace_geteventmsg(pid: types::Pid) -> Result<types::ULong> {
+    let mut msg = MaybeUninit::<types::ULong>::uninit();
+    ptrace(c::PTRACE_GETEVENTMSG, pid, NULL!(), void_ptr!(msg.as_mut_ptr()))?;
+    Ok(unsafe { msg.assume_init() })
+}
+
 fn ptrace_geteventmsg_get_child_pid(parent: types::Pid) -> Result<types::Pid> {
-    let mut pid = MaybeUninit::<types::ULong>::uninit();
-    ptrace(c::PTRACE_GETEVENTMSG, parent, NULL!(), void_ptr!(pid.as_mut_ptr()))?;
-    let pid = unsafe { pid.assume_init() };
-    Ok(pid as types::Pid)
+    Ok(ptrace_geteventmsg(parent)? as types::Pid)
+}
+
+fn ptrace_getsiginfo(pid: types::Pid) -> Result<SignalInfo> {
+    let mut info = SignalInfo{ buf: [0; SIGINFO_SIZE] };
+    ptrace(c::PTRACE_GETSIGINFO, pid, NULL!(), void_ptr!(info.buf.as_mut_ptr()))?;
+    Ok(info)
 }
 
 fn ptrace_attach(pid: types::Pid) -> Result<()> {
-    const OPT: i32 = c::PTRACE_O_TRACEEXEC | c::PTRACE_O_TRACEFORK | c::PTRACE_O_TRACEVFORK | c::PTRACE_O_TRACECLONE | c::PTRACE_O_TRACESYSGOOD;
-    ptrace(c::PTRACE_SEIZE, pid, NULL!(), void_ptr!(OPT))
+    const OPT: i32 = c::PTRACE_O_TRACEEXEC | c::PTRACE_O_TRACESYSGOOD;
+    let opt = OPT | follow_option();
+    let opt = if is_seccomp_mode() { opt | c::PTRACE_O_TRACESECCOMP } else { opt };
+    ptrace(c::PTRACE_SEIZE, pid, NULL!(), void_ptr!(opt))
+}
+
+fn ptrace_detach(pid: types::Pid) -> Result<()> {
+    ptrace2(c::PTRACE_DETACH, pid)
+}
+
+fn ptrace_detach_sig(pid: types::Pid, sig: types::SInt) -> Result<()> {
+    ptrace(c::PTRACE_DETACH, pid, NULL!(), void_ptr!(sig))
 }
 
 fn ptrace_get_syscall_info(pid: types::Pid) -> Result<ptrace_syscall_info> {
@@ -187,7 +284,51 @@ unsafe fn peek_readv(pid: types::Pid, addr: types::Ptr, dst: *mut u8, size: usiz
 }
 
 unsafe fn peek_buf(pid: types::Pid, addr: types::Ptr, dst: *mut u8, size: usize) -> Result<usize> {
-    Ok(peek_readv(pid, addr, dst, size).unwrap())
+    if record::is_replay() {
+        return record::replayed_mem(pid, addr, dst, size);
+    }
+    let len = peek_readv(pid, addr, dst, size)?;
+    record::record(BLOB::MEM, pid, addr as u64, std::slice::from_raw_parts(dst, len));
+    Ok(len)
+}
+
+unsafe fn poke_writev(pid: types::Pid, addr: types::Ptr, src: *const u8, size: usize) -> Result<usize> {
+    let local = c::iovec{iov_base: src as *mut types::Void, iov_len: size};
+    let remote = c::iovec{iov_base: addr as *mut types::Void, iov_len: size};
+    match c::process_vm_writev(pid, &local, 1, &remote, 1, 0) {
+        res if res < 0 => Err(Error::last_os_error()),
+        res => Ok(res as usize),
+    }
+}
+
+fn ptrace_peekdata(pid: types::Pid, addr: types::Ptr) -> Result<types::ULong> {
+    // -1 is valid data, so error is known by errno
+    unsafe {
+        *c::__errno_location() = 0;
+        let r = c::ptrace(c::PTRACE_PEEKDATA, pid, void_ptr!(addr), NULL!());
+        match *c::__errno_location() {
+            0 => Ok(r as types::ULong),
+            _ => Err(Error::last_os_error()),
+        }
+    }
+}
+
+fn ptrace_pokedata(pid: types::Pid, addr: types::Ptr, data: types::ULong) -> Result<()> {
+    ptrace(c::PTRACE_POKEDATA, pid, void_ptr!(addr), void_ptr!(data))
+}
+
+/// Write per word by PTRACE_POKEDATA, bytes after end in the last word are kept
+fn poke_by_word(pid: types::Pid, addr: types::Ptr, src: &[u8]) -> Result<()> {
+    const W: usize = std::mem::size_of::<types::ULong>();
+    let mut pos = 0;
+    while pos < src.len() {
+        let n = std::cmp::min(W, src.len() - pos);
+        let mut word = if n < W { ptrace_peekdata(pid, addr + pos)?.to_ne_bytes() } else { [0; W] };
+        word[..n].copy_from_slice(&src[pos..pos + n]);
+        ptrace_pokedata(pid, addr + pos, types::ULong::from_ne_bytes(word))?;
+        pos += n;
+    }
+    Ok(())
 }
 
 fn sigstop_self() -> std::io::Result<()> {
@@ -220,6 +361,14 @@ fn exit_status(status: types::SInt) -> types::SInt {
     (status & 0xff00) >> 8
 }
 
+fn term_signal(status: types::SInt) -> types::SInt {
+    status & 0x7f
+}
+
+fn is_core_dumped_status(status: types::SInt) -> bool {
+    (status & 0x80) != 0
+}
+
 fn signal_status(status: types::SInt) -> types::SInt {
     (status & 0xff00) >> 8
 }
@@ -237,6 +386,12 @@ fn is_syscall_stopped_status(status: types::SInt) -> bool {
     is_stopped_status(status) && (exit_status(status) == SYSCALLED)
 }
 
+fn is_seccomp_stopped_status(status: types::SInt) -> bool {
+    const SECCOMPED: types::SInt = c::SIGTRAP | (c::PTRACE_EVENT_SECCOMP << 8);
+    let sig = status >> 8;
+    is_stopped_status(status) && (sig == SECCOMPED)
+}
+
 fn is_event_stop(status: types::SInt) -> bool {
     (status >> 16) == c::PTRACE_EVENT_STOP
 }
@@ -261,16 +416,79 @@ fn is_exec_stop(status: types::SInt) -> bool {
     is_stopped_status(status) && (sig == EXECED)
 }
 
+const SIGINFO_SIZE: usize = 128;
+
+/// Raw siginfo_t of signal-delivery-stop, the layout is the tracer's native one
+#[repr(C, align(8))]
+pub struct SignalInfo {
+    buf: [u8; SIGINFO_SIZE],
+}
+
+impl SignalInfo {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Create from bytes taken by `as_bytes`, e.g. recorded siginfo
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(SignalInfo{ buf: bytes.try_into().ok()? })
+    }
+
+    /// Check whether the signal is sent by kill() of this tracer, e.g. SIGCONT to start traced child
+    fn is_sent_by_self(&self) -> bool {
+        const SI_USER: types::SInt = 0;
+        let field = |offset: usize| types::SInt::from_ne_bytes(self.buf[offset..offset + 4].try_into().unwrap());
+        // si_pid follows si_signo, si_errno, si_code and union alignment
+        let pid_offset = std::cmp::max(12, std::mem::size_of::<types::Ptr>() * 2);
+        field(8) == SI_USER && field(pid_offset) == getpid()
+    }
+}
+
 /// Peek event types
 pub enum ChildEventKind {
     /// Stop event for process fork/vfork/clone
     ForkStop,
-    /// Event for process exit
-    ExitDone,
-    /// Stop event for process exit by signal
-    SigExited,
+    /// Event for process exit with exit status
+    ExitDone(types::SInt),
+    /// Event for process exit by signal with the signal number and whether core dumped
+    SigExited(types::SInt, bool),
     /// Stop event for syscall-enter or syscall-exit
     SyscallStop,
+    /// Stop event for signal delivery, the signal shall be passed by `cont_process_with_signal`
+    SignalStop(types::SInt, SignalInfo),
+    /// Stop event for successful execve with former thread ID, the thread ID changes if non-leader thread execs
+    ExecStop(types::Pid),
+}
+
+/// Convert wait status to peek event, None if the stop is handled here and the tracee is restarted
+fn to_event(pid: types::Pid, status: types::SInt) -> Result<Option<ChildEventKind>> {
+    if is_fork_stopped_status(status) {
+        return Ok(Some(ChildEventKind::ForkStop));
+    } else if is_seccomp_stopped_status(status) {
+        return Ok(Some(ChildEventKind::SyscallStop));
+    } else if is_group_stop(status) {
+        ptrace_listen(pid)?;
+    } else if is_ptrace_event_stop(status) {
+        ptrace_syscall(pid)?;
+    } else if is_exec_stop(status) {
+        let former = ptrace_geteventmsg(pid).map(|t| t as types::Pid).unwrap_or(pid);
+        return Ok(Some(ChildEventKind::ExecStop(former)));
+    } else if is_exited_status(status) {
+        return Ok(Some(ChildEventKind::ExitDone(exit_status(status))));
+    } else if is_sigexited_status(status) {
+        return Ok(Some(ChildEventKind::SigExited(term_signal(status), is_core_dumped_status(status))));
+    } else if is_syscall_stopped_status(status) {
+        return Ok(Some(ChildEventKind::SyscallStop));
+    } else if is_stopped_status(status) {
+        let sig = signal_status(status);
+        match ptrace_getsiginfo(pid) {
+            Ok(info) if sig == c::SIGCONT && info.is_sent_by_self() => { ptrace_syscall_sig(pid, sig)?; },
+            Ok(info) => return Ok(Some(ChildEventKind::SignalStop(sig, info))),
+            // not in signal-delivery-stop, e.g. killed while stopping
+            Err(_) => { let _ = ptrace_syscall_sig(pid, sig); },
+        }
+    }
+    Ok(None)
 }
 
 /// Wait for peek event
@@ -279,28 +497,61 @@ pub fn wait_event() -> Result<(types::Pid, ChildEventKind)> {
         let r = waiter::wait_any();
         if r.is_err() {
             let r = Error::last_os_error();
-            if r.raw_os_error().unwrap() == c::ECHILD {
+            let errno = r.raw_os_error().unwrap();
+            if errno == c::ECHILD || errno == c::EINTR {
                 return Err(r);
             }
             continue;
         }
         let (pid, status) = r.unwrap();
-        if is_fork_stopped_status(status) {
-            return Ok((pid, ChildEventKind::ForkStop));
-        } else if is_group_stop(status) {
-            ptrace_listen(pid)?;
-        } else if is_ptrace_event_stop(status) {
-            ptrace_syscall(pid)?;
-        } else if is_exec_stop(status) {
-            ptrace_syscall(pid)?;
-        } else if is_exited_status(status) {
-            return Ok((pid, ChildEventKind::ExitDone));
-        } else if is_sigexited_status(status) {
-            return Ok((pid, ChildEventKind::SigExited));
-        } else if is_syscall_stopped_status(status) {
-            return Ok((pid, ChildEventKind::SyscallStop));
-        } else if is_stopped_status(status) {
-            ptrace_syscall_sig(pid, signal_status(status))?;
+        if let Some(event) = to_event(pid, status)? {
+            return Ok((pid, event));
+        }
+    }
+}
+
+/// Wait for peek event until timeout, None if timed out or interrupted by signal
+/// # Arguments
+/// * `timeout` - Time to wait
+pub fn wait_event_timeout(timeout: Duration) -> Result<Option<(types::Pid, ChildEventKind)>> {
+    let deadline = Instant::now() + timeout;
+    let mut set: c::sigset_t = [0; std::mem::size_of::<c::sigset_t>() / std::mem::size_of::<types::ULong>()];
+    let mut old = set;
+    // waitpid has no timeout, so wait for SIGCHLD, it is kept pending while blocked and not lost before waiting
+    unsafe {
+        c::sigemptyset(&mut set);
+        c::sigaddset(&mut set, c::SIGCHLD);
+        c::pthread_sigmask(c::SIG_BLOCK, &set, &mut old);
+    }
+    let r = wait_event_until(deadline, &set);
+    unsafe { c::pthread_sigmask(c::SIG_SETMASK, &old, std::ptr::null_mut()); }
+    r
+}
+
+fn wait_event_until(deadline: Instant, sigchld: &c::sigset_t) -> Result<Option<(types::Pid, ChildEventKind)>> {
+    loop {
+        match waiter::wait_any_nohang()? {
+            (0, _) => {},
+            (pid, status) => {
+                if let Some(event) = to_event(pid, status)? {
+                    return Ok(Some((pid, event)));
+                }
+                continue;
+            },
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        let left = deadline - now;
+        let timeout = c::timespec{ tv_sec: left.as_secs() as types::SLong, tv_nsec: left.subsec_nanos() as types::SLong };
+        if unsafe { c::sigtimedwait(sigchld, std::ptr::null_mut(), &timeout) } < 0 {
+            let e = Error::last_os_error();
+            return match e.raw_os_error() {
+                // interrupted by other signal, e.g. SIGINT, caller checks it
+                Some(c::EAGAIN) | Some(c::EINTR) => Ok(None),
+                _ => Err(e),
+            };
         }
     }
 }
@@ -338,6 +589,8 @@ pub struct SyscallSummery {
     ret: Option<SyscallRet>,
     uni: arch::sys_uni::NR,
     is64: bool,
+    entry_time: Instant,
+    exit_time: Option<Instant>,
 }
 
 /// Syscall argument's number specifier
@@ -353,7 +606,7 @@ impl SyscallSummery {
         let nr = args.nr;
         let is64 = entry.is64;
         let uni = if is64 { arch::sys_uni::a64::to_uni(nr) } else { arch::sys_uni::a32::to_uni(nr) };
-        SyscallSummery{ args, ret, uni, is64 }
+        SyscallSummery{ args, ret, uni, is64, entry_time: Instant::now(), exit_time: None }
     }
 
     /// Override by syscall-enter information and forget syscall-exit summery
@@ -364,13 +617,26 @@ impl SyscallSummery {
         self.ret = None;
         self.is64 = entry.is64;
         self.uni = if self.is64 { arch::sys_uni::a64::to_uni(self.args.nr) } else { arch::sys_uni::a32::to_uni(self.args.nr) };
+        self.entry_time = Instant::now();
+        self.exit_time = None;
     }
 
     /// Create dummy summery, used for socketcall etc
     pub fn new_dummy_entry(is64: bool, uni: arch::sys_uni::NR, nr: u64, args: [u64; 6], ret:i64) -> Self {
         let args = SyscallArg{nr, args};
         let ret = Some(if ret >= 0 || ret < -4096 { SyscallRet::OK(ret) } else { SyscallRet::ERR(ret as i32) });
-        SyscallSummery{ args, ret, uni, is64 }
+        let now = Instant::now();
+        SyscallSummery{ args, ret, uni, is64, entry_time: now, exit_time: Some(now) }
+    }
+
+    /// Create from recorded summery
+    /// # Arguments
+    /// * `ret` - Return value, or errno if failed, None at syscall-entry
+    pub fn new_recorded(is64: bool, nr: u64, args: [u64; 6], ret: Option<std::result::Result<i64, i32>>, entry_time: Instant, exit_time: Option<Instant>) -> Self {
+        let args = SyscallArg{nr, args};
+        let ret = ret.map(|r| match r { Ok(v) => SyscallRet::OK(v), Err(e) => SyscallRet::ERR(e) });
+        let uni = if is64 { arch::sys_uni::a64::to_uni(nr) } else { arch::sys_uni::a32::to_uni(nr) };
+        SyscallSummery{ args, ret, uni, is64, entry_time, exit_time }
     }
 
     /// Add summery from syscall-exit information
@@ -378,6 +644,22 @@ impl SyscallSummery {
     /// * `exit` - syscall-exit information
     pub fn add_exit(&mut self, exit: SyscallInfoExit) {
         self.ret = Some(exit.ret);
+        self.exit_time = Some(Instant::now());
+    }
+
+    /// Get time of syscall-enter stop
+    pub fn entry_time(&self) -> Instant {
+        self.entry_time
+    }
+
+    /// Get time of syscall-exit stop, None if not exited yet
+    pub fn exit_time(&self) -> Option<Instant> {
+        self.exit_time
+    }
+
+    /// Get time spent between syscall-enter and syscall-exit stop
+    pub fn duration(&self) -> Option<Duration> {
+        self.exit_time.map(|t| t.duration_since(self.entry_time))
     }
 
     /// Get raw syscall number
@@ -425,25 +707,51 @@ impl SyscallSummery {
     pub fn is_64(&self) -> bool {
         self.is64
     }
+
+    /// Get return value, or errno if failed, None at syscall-entry
+    pub fn raw_return(&self) -> Option<std::result::Result<i64, i32>> {
+        match self.ret {
+            Some(SyscallRet::OK(v)) => Some(Ok(v)),
+            Some(SyscallRet::ERR(e)) => Some(Err(e)),
+            None => None,
+        }
+    }
 }
 
 /// Execute process and trace it
 /// # Arguments
 /// * `cmd` - Execute command name
 /// * `args` - Execute command's arguments
-pub fn peek_attach_exec_child<T>(cmd: String, args: T) -> Result<types::Pid>
+/// * `filter` - Seccomp filter installed before execute, stop only on filtered syscalls if specified
+/// * `exec_env` - User, environment and working directory of the command
+pub fn peek_attach_exec_child<T>(cmd: String, args: T, filter: Option<&SeccompFilter>, exec_env: &ExecEnv) -> Result<types::Pid>
 where
     T: Iterator<Item = String>
 {
+    if filter.is_some() {
+        SECCOMP_MODE.store(true, Ordering::Relaxed);
+    }
     match unsafe { c::fork() } {
         -1 => Err(Error::last_os_error()),
         0 => {
+            if let Err(e) = exec_env.apply() {
+                eprintln!("{}", e);
+                unsafe { c::_exit(1) };
+            }
             sigstop_self().unwrap();
+            if let Some(filter) = filter {
+                if let Err(e) = filter.install() {
+                    eprintln!("failed to install seccomp filter: {}", e);
+                    unsafe { c::_exit(1) };
+                }
+            }
             Command::new(cmd).args(args).exec();
             panic!();
         },
         pid => {
-            wait_one_stop_no_status(pid).unwrap();
+            if !is_stopped_status(waiter::wait_one_stop(pid)?) {
+                return Err(Error::new(ErrorKind::Other, format!("failed to start {}", cmd)));
+            }
             ptrace_attach(pid).unwrap();
             sigcont_process(pid).unwrap();
             Ok(pid)
@@ -460,6 +768,38 @@ pub fn peek_attach_running_process(pid: types::Pid) -> Result<()> {
     Ok(())
 }
 
+fn get_tids(pid: types::Pid) -> Result<Vec<types::Pid>> {
+    let tids = std::fs::read_dir(format!("/proc/{}/task", pid))?
+        .filter_map(|e| e.ok()?.file_name().to_str()?.parse::<types::Pid>().ok())
+        .collect();
+    Ok(tids)
+}
+
+/// Start trace of all threads of the runnning process, return attached thread IDs
+/// # Arguments
+/// * `pid` - A target process ID
+pub fn peek_attach_running_threads(pid: types::Pid) -> Result<Vec<types::Pid>> {
+    let mut seen = std::collections::BTreeSet::new();
+    let mut attached = vec![];
+    // threads created by not yet attached thread are found at next scan, repeat until no new thread
+    loop {
+        let tids: Vec<types::Pid> = get_tids(pid)?.into_iter().filter(|t| !seen.contains(t)).collect();
+        if tids.is_empty() {
+            break;
+        }
+        for tid in tids {
+            seen.insert(tid);
+            match peek_attach_running_process(tid) {
+                Ok(()) => attached.push(tid),
+                Err(e) if tid == pid => return Err(e),
+                // exited, or already traced by clone event of attached thread
+                Err(_) => {},
+            }
+        }
+    }
+    Ok(attached)
+}
+
 /// Get cloned/forked/vforked child process ID and restart parent process
 /// # Arguments
 /// * `pid` - Parent process ID
@@ -471,10 +811,72 @@ pub fn treat_stopped_clone_process(parent: types::Pid) -> Result<types::Pid>
     Ok(pid)
 }
 
-/// Restart syscall-stopped target process
+/// Stop trace of ptrace-stopped target process and restart it
+/// # Arguments
+/// * `pid` - A target process ID
+pub fn detach_process(pid: types::Pid) -> Result<()> {
+    ptrace_detach(pid)
+}
+
+/// Signal of signal-delivery-stop to be delivered at detach, 0 for other stops
+fn pending_signal(pid: types::Pid, status: types::SInt) -> types::SInt {
+    if !is_stopped_status(status) || (status >> 16) != 0 || is_syscall_stopped_status(status) {
+        return 0;
+    }
+    match ptrace_getsiginfo(pid) {
+        Ok(info) if !info.is_sent_by_self() => signal_status(status),
+        _ => 0,
+    }
+}
+
+/// Stop trace of running target processes, they are interrupted and detached with pending signal
+/// # Arguments
+/// * `pids` - Target thread IDs
+pub fn interrupt_and_detach(pids: &[types::Pid]) {
+    pids.iter().for_each(|pid| { let _ = ptrace_interrupt(*pid); });
+    // stopped in any order, e.g. parent of vfork is not stopped until its stopped child is detached
+    let mut rest: std::collections::BTreeSet<types::Pid> = pids.iter().copied().collect();
+    while !rest.is_empty() {
+        match waiter::wait_any() {
+            Ok((pid, status)) => {
+                rest.remove(&pid);
+                // also child created before interrupted
+                if !is_exited_status(status) && !is_sigexited_status(status) {
+                    let _ = ptrace_detach_sig(pid, pending_signal(pid, status));
+                }
+            },
+            Err(e) if e.raw_os_error() == Some(c::EINTR) => continue,
+            Err(_) => break,
+        }
+    }
+}
+
+/// Restart syscall-stopped target process until next syscall to trace
 /// # Arguments
 /// * `pid` - A target process ID
 pub fn cont_process(pid: types::Pid) -> Result<()> {
+    if is_seccomp_mode() { ptrace_cont(pid) } else { ptrace_syscall(pid) }
+}
+
+/// Restart syscall-stopped target process without syscall stop, until next process event e.g. fork, exec and signal
+/// # Arguments
+/// * `pid` - A target process ID
+pub fn cont_process_to_event(pid: types::Pid) -> Result<()> {
+    ptrace_cont(pid)
+}
+
+/// Restart signal-delivery-stopped target process with the signal delivered
+/// # Arguments
+/// * `pid` - A target process ID
+/// * `sig` - A signal number to deliver, 0 suppresses the signal
+pub fn cont_process_with_signal(pid: types::Pid, sig: types::SInt) -> Result<()> {
+    if is_seccomp_mode() { ptrace_cont_sig(pid, sig) } else { ptrace_syscall_sig(pid, sig) }
+}
+
+/// Restart syscall-enter-stopped target process until syscall-exit
+/// # Arguments
+/// * `pid` - A target process ID
+pub fn cont_process_to_exit(pid: types::Pid) -> Result<()> {
     ptrace_syscall(pid)
 }
 
@@ -485,7 +887,10 @@ pub fn peek_syscall_info(pid: types::Pid) -> Result<SyscallInfo> {
     let r = ptrace_get_syscall_info(pid)?;
     unsafe {
         match r.op as types::SInt {
+            // syscall-enter by PTRACE_SYSCALL restart for signal etc, seccomp stop shall follow if need
+            c::PTRACE_SYSCALL_INFO_ENTRY if is_seccomp_mode() => Err(Error::from(ErrorKind::Other)),
             c::PTRACE_SYSCALL_INFO_ENTRY => Ok(SyscallInfo::ENTRY(r.to_rust_entry())),
+            c::PTRACE_SYSCALL_INFO_SECCOMP => Ok(SyscallInfo::ENTRY(r.to_rust_entry())),
             c::PTRACE_SYSCALL_INFO_EXIT => Ok(SyscallInfo::EXIT(r.to_rust_exit())),
             _ => Err(Error::from(ErrorKind::Other)),
         }
@@ -499,7 +904,9 @@ pub fn peek_syscall_info(pid: types::Pid) -> Result<SyscallInfo> {
 pub fn peek_data<T>(pid: types::Pid, addr: types::Ptr) -> Result<T> {
     let size = std::mem::size_of::<T>();
     let mut buf = MaybeUninit::<T>::uninit();
-    unsafe { peek_buf(pid, addr, buf.as_mut_ptr().cast::<u8>(), size)?; }
+    if unsafe { peek_buf(pid, addr, buf.as_mut_ptr().cast::<u8>(), size)? } != size {
+        return Err(Error::from_raw_os_error(c::EFAULT));
+    }
     Ok(unsafe { buf.assume_init() })
 }
 
@@ -514,7 +921,12 @@ pub fn peek_until_null(pid: types::Pid, addr: types::Ptr) -> Result<Vec<u8>> {
         const PEEK_SIZE: usize = 32;
         let mut buf = Vec::<u8>::with_capacity(PEEK_SIZE);
         unsafe {
-            let len = peek_buf(pid, addr, buf.as_mut_ptr(), buf.capacity())?;
+            // chunk may start at unreadable page after the string read so far
+            let len = match peek_buf(pid, addr, buf.as_mut_ptr(), buf.capacity()) {
+                Ok(len) => len,
+                Err(_) if !res.is_empty() => 0,
+                Err(e) => return Err(e),
+            };
             buf.set_len(len);
         }
         let error = buf.len() != buf.capacity();
@@ -542,9 +954,99 @@ pub fn peek_vec(pid: types::Pid, addr: types::Ptr, dst:&mut Vec<u8>, size: usize
     let size = std::cmp::min(size, dst.capacity());
     unsafe {
         let ptr = dst.as_mut_ptr();
-        peek_buf(pid, addr, ptr, size)?;
+        if peek_buf(pid, addr, ptr, size)? != size {
+            return Err(Error::from_raw_os_error(c::EFAULT));
+        }
         dst.set_len(size);
     }
     Ok(())
 }
 
+/// Write data to target process, by PTRACE_POKEDATA if process_vm_writev failed, e.g. read-only mapping
+/// # Arguments
+/// * `pid` - A poke target process ID
+/// * `addr` - A poke target address
+/// * `src` - Data to write
+pub fn poke_vec(pid: types::Pid, addr: types::Ptr, src: &[u8]) -> Result<()> {
+    match unsafe { poke_writev(pid, addr, src.as_ptr(), src.len()) } {
+        Ok(n) if n == src.len() => Ok(()),
+        _ => poke_by_word(pid, addr, src),
+    }
+}
+
+/// Get thread group ID (process ID) of thread from /proc/<tid>/status
+/// # Arguments
+/// * `tid` - A thread ID
+pub fn get_tgid(tid: types::Pid) -> Result<types::Pid> {
+    if record::is_replay() {
+        let tgid = record::replayed(BLOB::TGID, tid, 0)?;
+        return tgid.try_into().map(types::Pid::from_ne_bytes).map_err(|_| Error::from(ErrorKind::InvalidData));
+    }
+    let status = std::fs::read_to_string(format!("/proc/{}/status", tid))?;
+    let tgid = status.lines()
+        .find_map(|l| l.strip_prefix("Tgid:"))
+        .and_then(|v| v.trim().parse::<types::Pid>().ok())
+        .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+    record::record(BLOB::TGID, tid, 0, &tgid.to_ne_bytes());
+    Ok(tgid)
+}
+
+/// Get command name of process from /proc/<pid>/comm
+/// # Arguments
+/// * `pid` - A process ID
+pub fn get_comm(pid: types::Pid) -> Result<Vec<u8>> {
+    if record::is_replay() {
+        return record::replayed(BLOB::COMM, pid, 0);
+    }
+    let mut comm = std::fs::read(format!("/proc/{}/comm", pid))?;
+    if comm.last() == Some(&b'\n') {
+        comm.pop();
+    }
+    record::record(BLOB::COMM, pid, 0, &comm);
+    Ok(comm)
+}
+
+/// Get path or description of fd from /proc/<pid>/fd/<fd>
+/// # Arguments
+/// * `pid` - A process ID
+/// * `fd` - A file descriptor of the process
+pub fn get_fd_link(pid: types::Pid, fd: types::SInt) -> Result<Vec<u8>> {
+    use std::os::unix::ffi::OsStringExt;
+    if record::is_replay() {
+        return record::replayed(BLOB::FDLINK, pid, fd as u64);
+    }
+    let link = std::fs::read_link(format!("/proc/{}/fd/{}", pid, fd))?.into_os_string().into_vec();
+    record::record(BLOB::FDLINK, pid, fd as u64, &link);
+    Ok(link)
+}
+
+/// Get working directory of process from /proc/<pid>/cwd
+/// # Arguments
+/// * `pid` - A process ID
+pub fn get_cwd(pid: types::Pid) -> Result<Vec<u8>> {
+    use std::os::unix::ffi::OsStringExt;
+    if record::is_replay() {
+        return record::replayed(BLOB::CWD, pid, 0);
+    }
+    let cwd = std::fs::read_link(format!("/proc/{}/cwd", pid))?.into_os_string().into_vec();
+    record::record(BLOB::CWD, pid, 0, &cwd);
+    Ok(cwd)
+}
+
+/// Get executable path of process from /proc/<pid>/exe
+/// # Arguments
+/// * `pid` - A process ID
+pub fn get_exe(pid: types::Pid) -> Result<Vec<u8>> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(std::fs::read_link(format!("/proc/{}/exe", pid))?.into_os_string().into_vec())
+}
+
+/// Get first command line argument of process from /proc/<pid>/cmdline
+/// # Arguments
+/// * `pid` - A process ID
+pub fn get_argv0(pid: types::Pid) -> Result<Vec<u8>> {
+    let mut cmdline = std::fs::read(format!("/proc/{}/cmdline", pid))?;
+    let len = cmdline.iter().position(|c| *c == 0).unwrap_or(cmdline.len());
+    cmdline.truncate(len);
+    Ok(cmdline)
+}
@@ -0,0 +1,162 @@
+//! Record of data read from tracee, to output recorded trace later without tracee
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{Result, Error, ErrorKind};
+
+/// Kind of recorded data
+#[derive(Clone, Copy, PartialEq)]
+pub enum BLOB {
+    /// Tracee memory, key is address
+    MEM = 1,
+    /// Thread group ID, data is native bytes of pid
+    TGID = 2,
+    /// Command name
+    COMM = 3,
+    /// Link of /proc/<pid>/fd/<fd>, key is fd
+    FDLINK = 4,
+    /// Link of /proc/<pid>/cwd
+    CWD = 5,
+}
+
+impl BLOB {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            1 => Some(BLOB::MEM),
+            2 => Some(BLOB::TGID),
+            3 => Some(BLOB::COMM),
+            4 => Some(BLOB::FDLINK),
+            5 => Some(BLOB::CWD),
+            _ => None,
+        }
+    }
+}
+
+/// Data read from tracee or /proc
+pub struct Blob {
+    pub kind: BLOB,
+    pub pid: types::Pid,
+    pub key: u64,
+    pub data: Vec<u8>,
+}
+
+enum MODE {
+    /// Read from tracee
+    LIVE,
+    /// Read from tracee and keep read data until taken
+    RECORD(Vec<Blob>),
+    /// Read from recorded data, newest first per thread, thread group IDs are kept for all time
+    REPLAY(BTreeMap<types::Pid, Vec<Blob>>, BTreeMap<types::Pid, types::Pid>),
+}
+
+thread_local! {
+    static MODE: RefCell<MODE> = const { RefCell::new(MODE::LIVE) };
+}
+
+/// Start to keep data read from tracee, taken by `take_recorded`
+pub fn start_record() {
+    MODE.with(|m| *m.borrow_mut() = MODE::RECORD(vec![]));
+}
+
+/// Take data read from tracee since last taken
+pub fn take_recorded() -> Vec<Blob> {
+    MODE.with(|m| match &mut *m.borrow_mut() {
+        MODE::RECORD(blobs) => std::mem::take(blobs),
+        _ => vec![],
+    })
+}
+
+/// Start to read from recorded data instead of tracee, ptrace requests are ignored
+pub fn start_replay() {
+    MODE.with(|m| *m.borrow_mut() = MODE::REPLAY(BTreeMap::new(), BTreeMap::new()));
+}
+
+/// Add recorded data to read
+/// # Arguments
+/// * `pid` - A thread ID of the recorded event
+/// * `renew` - Forget data of former events of the thread, e.g. at syscall-entry
+/// * `blobs` - Data recorded with the event
+pub fn add_replayed(pid: types::Pid, renew: bool, blobs: Vec<Blob>) {
+    MODE.with(|m| {
+        let MODE::REPLAY(map, tgids) = &mut *m.borrow_mut() else { return };
+        if renew {
+            map.remove(&pid);
+        }
+        for b in blobs {
+            match b.kind {
+                BLOB::TGID => if let Ok(tgid) = b.data.as_slice().try_into().map(types::Pid::from_ne_bytes) {
+                    tgids.insert(b.pid, tgid);
+                },
+                _ => map.entry(b.pid).or_default().push(b),
+            }
+        }
+    })
+}
+
+pub(crate) fn is_replay() -> bool {
+    MODE.with(|m| matches!(*m.borrow(), MODE::REPLAY(..)))
+}
+
+pub(crate) fn record(kind: BLOB, pid: types::Pid, key: u64, data: &[u8]) {
+    MODE.with(|m| if let MODE::RECORD(blobs) = &mut *m.borrow_mut() {
+        blobs.push(Blob{ kind, pid, key, data: data.to_vec() });
+    })
+}
+
+/// Find recorded data other than memory
+pub(crate) fn replayed(kind: BLOB, pid: types::Pid, key: u64) -> Result<Vec<u8>> {
+    MODE.with(|m| {
+        let MODE::REPLAY(map, tgids) = &*m.borrow() else { return None };
+        if kind == BLOB::TGID {
+            return tgids.get(&pid).map(|t| t.to_ne_bytes().to_vec());
+        }
+        map.get(&pid)?.iter().rev().find(|b| b.kind == kind && b.key == key).map(|b| b.data.clone())
+    }).ok_or_else(|| Error::from(ErrorKind::NotFound))
+}
+
+/// Copy recorded memory, read covering the range is used, or partial read at the same address if none covers
+/// # Safety
+/// `dst` shall be valid for writes of `size` bytes
+pub(crate) unsafe fn replayed_mem(pid: types::Pid, addr: types::Ptr, dst: *mut u8, size: usize) -> Result<usize> {
+    MODE.with(|m| {
+        let MODE::REPLAY(map, _) = &*m.borrow() else { return None };
+        let addr = addr as u64;
+        let mems = || map.get(&pid).into_iter().flatten().rev().filter(|b| b.kind == BLOB::MEM);
+        // partial read is recorded at the end of readable memory, e.g. string read in chunks
+        let b = mems().find(|b| b.key <= addr && addr + size as u64 <= b.key + b.data.len() as u64)
+            .or_else(|| mems().find(|b| b.key == addr))?;
+        let src = &b.data[(addr - b.key) as usize..];
+        let len = std::cmp::min(src.len(), size);
+        std::ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+        Some(len)
+    }).ok_or_else(|| Error::from_raw_os_error(super::c::EFAULT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem(key: u64, data: &[u8]) -> Blob {
+        Blob{ kind: BLOB::MEM, pid: 1, key, data: data.to_vec() }
+    }
+
+    fn read(addr: u64, size: usize) -> Result<Vec<u8>> {
+        let mut buf = vec![0; size];
+        let len = unsafe { replayed_mem(1, addr as types::Ptr, buf.as_mut_ptr(), size)? };
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    #[test]
+    fn replayed_mem_covering() {
+        start_replay();
+        add_replayed(1, false, vec![mem(0x1000, b"0123456789abcdef"), mem(0x1000, b"01234567"), mem(0x2000, b"xyz")]);
+        // later shorter read at the same address does not hide covering read
+        assert_eq!(read(0x1000, 16).unwrap(), b"0123456789abcdef");
+        assert_eq!(read(0x1004, 4).unwrap(), b"4567");
+        assert_eq!(read(0x1008, 8).unwrap(), b"89abcdef");
+        // partial read at the same address, e.g. end of readable memory
+        assert_eq!(read(0x2000, 32).unwrap(), b"xyz");
+        assert!(read(0x1008, 16).is_err());
+        assert!(read(0x3000, 1).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Register access of syscall-stopped tracee by PTRACE_GETREGSET/PTRACE_SETREGSET
+use std::io::{Result, Error};
+use super::c;
+
+/// NT_PRSTATUS, general purpose registers
+const NT_PRSTATUS: usize = 1;
+
+/// Size of user_regs_struct of aarch64, the largest of supported architectures
+const REGS_MAX_SIZE: usize = 34 * 8;
+
+/// Size of native user_regs_struct
+#[cfg(target_arch = "x86_64")]
+const REGS_NATIVE_SIZE: usize = 27 * 8;
+#[cfg(target_arch = "x86")]
+const REGS_NATIVE_SIZE: usize = 17 * 4;
+#[cfg(target_arch = "aarch64")]
+const REGS_NATIVE_SIZE: usize = 34 * 8;
+#[cfg(target_arch = "arm")]
+const REGS_NATIVE_SIZE: usize = 18 * 4;
+
+/// Stack area below stack pointer kept for leaf function
+const RED_ZONE: usize = 128;
+
+/// NT_ARM_SYSTEM_CALL, syscall number can not be changed by x8
+#[cfg(target_arch = "aarch64")]
+const NT_ARM_SYSTEM_CALL: usize = 0x404;
+
+/// PTRACE_SET_SYSCALL, syscall number can not be changed by r7
+#[cfg(target_arch = "arm")]
+const PTRACE_SET_SYSCALL: types::SInt = 23;
+
+/// Raw user_regs_struct, its layout is native or compat of 32bit tracee
+struct Regs {
+    buf: [u8; REGS_MAX_SIZE],
+    len: usize,
+}
+
+impl Regs {
+    fn get(pid: types::Pid) -> Result<Self> {
+        let mut regs = Regs{ buf: [0; REGS_MAX_SIZE], len: REGS_MAX_SIZE };
+        let mut iov = c::iovec{ iov_base: regs.buf.as_mut_ptr().cast(), iov_len: regs.buf.len() };
+        super::ptrace(c::PTRACE_GETREGSET, pid, void_ptr!(NT_PRSTATUS), void_ptr!(&mut iov as *mut c::iovec))?;
+        regs.len = iov.iov_len;
+        Ok(regs)
+    }
+
+    fn set(&mut self, pid: types::Pid) -> Result<()> {
+        let mut iov = c::iovec{ iov_base: self.buf.as_mut_ptr().cast(), iov_len: self.len };
+        super::ptrace(c::PTRACE_SETREGSET, pid, void_ptr!(NT_PRSTATUS), void_ptr!(&mut iov as *mut c::iovec))
+    }
+
+    /// Registers of 32bit tracee on 64bit tracer, i386 layout on x86_64 and arm layout on aarch64
+    fn is_compat(&self) -> bool {
+        cfg!(target_pointer_width = "64") && self.len != REGS_NATIVE_SIZE
+    }
+
+    /// Register width, 4 bytes for 32bit tracer or compat registers
+    fn word_size(&self) -> usize {
+        if cfg!(target_pointer_width = "64") && !self.is_compat() { 8 } else { 4 }
+    }
+
+    /// Index of return value register, rax/eax/x0/r0
+    fn ret_index(&self) -> usize {
+        if cfg!(target_arch = "x86_64") && !self.is_compat() {
+            10
+        } else if cfg!(any(target_arch = "x86_64", target_arch = "x86")) {
+            6
+        } else {
+            0
+        }
+    }
+
+    /// Index of syscall number register, orig_rax/orig_eax
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    fn nr_index(&self) -> usize {
+        if cfg!(target_arch = "x86_64") && !self.is_compat() { 15 } else { 11 }
+    }
+
+    /// Index of argument registers, i386 layout uses ebx, ecx, edx, esi, edi, ebp
+    fn arg_index(&self) -> [usize; 6] {
+        if cfg!(target_arch = "x86_64") && !self.is_compat() { [14, 13, 12, 7, 9, 8] } else { [0, 1, 2, 3, 4, 5] }
+    }
+
+    /// Index of stack pointer, rsp/esp/sp/r13
+    fn sp_index(&self) -> usize {
+        if cfg!(target_arch = "x86_64") && !self.is_compat() {
+            19
+        } else if cfg!(any(target_arch = "x86_64", target_arch = "x86")) {
+            15
+        } else if cfg!(target_arch = "aarch64") && !self.is_compat() {
+            31
+        } else {
+            13
+        }
+    }
+
+    fn read(&self, index: usize) -> u64 {
+        let pos = index * self.word_size();
+        if self.word_size() == 8 {
+            u64::from_ne_bytes(self.buf[pos..pos + 8].try_into().unwrap())
+        } else {
+            u32::from_ne_bytes(self.buf[pos..pos + 4].try_into().unwrap()) as u64
+        }
+    }
+
+    fn write(&mut self, index: usize, value: i64) {
+        let size = self.word_size();
+        let pos = index * size;
+        if size == 8 {
+            self.buf[pos..pos + 8].copy_from_slice(&value.to_ne_bytes());
+        } else {
+            self.buf[pos..pos + 4].copy_from_slice(&(value as i32).to_ne_bytes());
+        }
+    }
+}
+
+/// Change syscall number at syscall-entry
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+fn set_syscall_nr(pid: types::Pid, nr: i64) -> Result<()> {
+    let mut regs = Regs::get(pid)?;
+    regs.write(regs.nr_index(), nr);
+    regs.set(pid)
+}
+
+#[cfg(target_arch = "aarch64")]
+fn set_syscall_nr(pid: types::Pid, nr: i64) -> Result<()> {
+    let mut nr = nr as types::SInt;
+    let mut iov = c::iovec{ iov_base: (&mut nr as *mut types::SInt).cast(), iov_len: std::mem::size_of::<types::SInt>() };
+    super::ptrace(c::PTRACE_SETREGSET, pid, void_ptr!(NT_ARM_SYSTEM_CALL), void_ptr!(&mut iov as *mut c::iovec))
+}
+
+#[cfg(target_arch = "arm")]
+fn set_syscall_nr(pid: types::Pid, nr: i64) -> Result<()> {
+    super::ptrace(PTRACE_SET_SYSCALL, pid, NULL!(), void_ptr!(nr as types::SInt))
+}
+
+/// Skip syscall at syscall-entry by changing syscall number to -1, syscall-exit stop still occurs
+/// # Arguments
+/// * `pid` - A syscall-entry stopped thread ID
+pub fn skip_syscall(pid: types::Pid) -> Result<()> {
+    set_syscall_nr(pid, -1)
+}
+
+/// Overwrite return value at syscall-exit, error is returned as -errno
+/// # Arguments
+/// * `pid` - A syscall-exit stopped thread ID
+/// * `value` - A return value
+pub fn set_return_value(pid: types::Pid, value: i64) -> Result<()> {
+    let mut regs = Regs::get(pid)?;
+    regs.write(regs.ret_index(), value);
+    regs.set(pid)
+}
+
+/// Replace string arguments at syscall-entry, strings are written to stack below red zone
+/// # Arguments
+/// * `pid` - A syscall-entry stopped thread ID
+/// * `args` - Argument positions and strings without terminating null
+pub fn replace_str_args(pid: types::Pid, args: &[(usize, Vec<u8>)]) -> Result<()> {
+    let mut regs = Regs::get(pid)?;
+    let size: usize = args.iter().map(|(_, s)| s.len() + 1).sum();
+    let sp = regs.read(regs.sp_index()) as usize;
+    let mut addr = sp.checked_sub(RED_ZONE + size).ok_or_else(|| Error::from_raw_os_error(c::EFAULT))? & !15;
+    let index = regs.arg_index();
+    for (n, s) in args {
+        super::poke_vec(pid, addr, &[s.as_slice(), b"\0"].concat())?;
+        regs.write(index[*n], addr as i64);
+        addr += s.len() + 1;
+    }
+    regs.set(pid)
+}
@@ -0,0 +1,206 @@
+//! Seccomp-BPF filter for stop only on selected syscalls
+use std::io::{Result, Error};
+
+mod c {
+    extern "C" {
+        pub fn prctl(option: types::SInt, arg2: types::ULong, arg3: types::ULong, arg4: types::ULong, arg5: types::ULong) -> types::SInt;
+    }
+
+    #[repr(C)]#[derive(Clone, Copy)]#[allow(non_camel_case_types)]
+    pub struct sock_filter {
+        pub code: u16,
+        pub jt: u8,
+        pub jf: u8,
+        pub k: u32,
+    }
+
+    #[repr(C)]#[allow(non_camel_case_types)]
+    pub struct sock_fprog {
+        pub len: types::UShrt,
+        pub filter: *const sock_filter,
+    }
+
+    pub const PR_SET_SECCOMP: types::SInt = 22;
+    pub const PR_SET_NO_NEW_PRIVS: types::SInt = 38;
+    pub const SECCOMP_MODE_FILTER: types::ULong = 2;
+
+    pub const BPF_MAXINSNS: usize = 4096;
+    pub const E2BIG: types::SInt = 7;
+
+    pub const BPF_LD: u16 = 0x00;
+    pub const BPF_JMP: u16 = 0x05;
+    pub const BPF_RET: u16 = 0x06;
+    pub const BPF_W: u16 = 0x00;
+    pub const BPF_ABS: u16 = 0x20;
+    pub const BPF_JEQ: u16 = 0x10;
+    pub const BPF_K: u16 = 0x00;
+
+    pub const SECCOMP_RET_TRACE: u32 = 0x7ff00000;
+    pub const SECCOMP_RET_ALLOW: u32 = 0x7fff0000;
+
+    /// offsetof(struct seccomp_data, nr)
+    pub const SECCOMP_DATA_NR: u32 = 0;
+    /// offsetof(struct seccomp_data, arch)
+    pub const SECCOMP_DATA_ARCH: u32 = 4;
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    pub const AUDIT_ARCH_64: u32 = 0xc000003e;
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    pub const AUDIT_ARCH_32: u32 = 0x40000003;
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+    pub const AUDIT_ARCH_64: u32 = 0xc00000b7;
+    #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
+    pub const AUDIT_ARCH_32: u32 = 0x40000028;
+}
+
+const fn stmt(code: u16, k: u32) -> c::sock_filter {
+    c::sock_filter{ code, jt: 0, jf: 0, k }
+}
+
+const fn jump(code: u16, k: u32, jt: u8, jf: u8) -> c::sock_filter {
+    c::sock_filter{ code, jt, jf, k }
+}
+
+/// Seccomp-BPF program, return SECCOMP_RET_TRACE for selected syscalls and allow others
+pub struct SeccompFilter {
+    prog: Vec<c::sock_filter>,
+}
+
+impl SeccompFilter {
+    fn push_arch(prog: &mut Vec<c::sock_filter>, arch: u32, nrs: &[u64]) {
+        // if (arch == $arch) { if (nr == $nrs[n]) return TRACE; ...; return ALLOW; }
+        prog.push(stmt(c::BPF_LD | c::BPF_W | c::BPF_ABS, c::SECCOMP_DATA_ARCH));
+        prog.push(jump(c::BPF_JMP | c::BPF_JEQ | c::BPF_K, arch, 1, 0));
+        let skip = prog.len();
+        prog.push(stmt(c::BPF_JMP, 0));
+        prog.push(stmt(c::BPF_LD | c::BPF_W | c::BPF_ABS, c::SECCOMP_DATA_NR));
+        for nr in nrs {
+            prog.push(jump(c::BPF_JMP | c::BPF_JEQ | c::BPF_K, *nr as u32, 0, 1));
+            prog.push(stmt(c::BPF_RET | c::BPF_K, c::SECCOMP_RET_TRACE));
+        }
+        prog.push(stmt(c::BPF_RET | c::BPF_K, c::SECCOMP_RET_ALLOW));
+        prog[skip].k = (prog.len() - skip - 1) as u32;
+    }
+
+    /// Create filter from raw syscall numbers
+    /// # Arguments
+    /// * `nr64` - Raw syscall numbers of 64bit architecture to stop
+    /// * `nr32` - Raw syscall numbers of 32bit architecture to stop
+    pub fn new(nr64: &[u64], nr32: &[u64]) -> Self {
+        let mut prog = vec![];
+        Self::push_arch(&mut prog, c::AUDIT_ARCH_64, nr64);
+        Self::push_arch(&mut prog, c::AUDIT_ARCH_32, nr32);
+        prog.push(stmt(c::BPF_RET | c::BPF_K, c::SECCOMP_RET_ALLOW));
+        SeccompFilter{ prog }
+    }
+
+    /// Check the program can be loaded by kernel
+    fn check(&self) -> Result<()> {
+        if self.prog.len() > c::BPF_MAXINSNS {
+            return Err(Error::from_raw_os_error(c::E2BIG));
+        }
+        Ok(())
+    }
+
+    /// Install filter to the calling process, the process shall be traced with PTRACE_O_TRACESECCOMP
+    pub fn install(&self) -> Result<()> {
+        self.check()?;
+        let fprog = c::sock_fprog{ len: self.prog.len() as types::UShrt, filter: self.prog.as_ptr() };
+        if unsafe { c::prctl(c::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } == -1 {
+            return Err(Error::last_os_error());
+        }
+        match unsafe { c::prctl(c::PR_SET_SECCOMP, c::SECCOMP_MODE_FILTER, &fprog as *const c::sock_fprog as types::ULong, 0, 0) } {
+            -1 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LD: u16 = c::BPF_LD | c::BPF_W | c::BPF_ABS;
+    const JA: u16 = c::BPF_JMP;
+    const JEQ: u16 = c::BPF_JMP | c::BPF_JEQ | c::BPF_K;
+    const RET: u16 = c::BPF_RET | c::BPF_K;
+
+    /// Run the program for syscall of arch as kernel does
+    fn run(filter: &SeccompFilter, arch: u32, nr: u32) -> u32 {
+        let mut pc = 0;
+        let mut acc = 0;
+        loop {
+            let i = filter.prog[pc];
+            pc += 1;
+            match i.code {
+                LD => acc = match i.k { c::SECCOMP_DATA_NR => nr, c::SECCOMP_DATA_ARCH => arch, _ => panic!("load {}", i.k) },
+                JA => pc += i.k as usize,
+                JEQ => pc += if acc == i.k { i.jt } else { i.jf } as usize,
+                RET => return i.k,
+                _ => panic!("code {:#x}", i.code),
+            }
+        }
+    }
+
+    #[test]
+    fn no_syscall() {
+        let filter = SeccompFilter::new(&[], &[]);
+        // load arch, jeq, ja, load nr, ret allow for each arch and ret allow
+        assert_eq!(filter.prog.len(), 11);
+        assert_eq!(filter.prog[2].k, 2);
+        assert_eq!(filter.prog[7].k, 2);
+        for nr in [0, 1, 59, 257] {
+            assert_eq!(run(&filter, c::AUDIT_ARCH_64, nr), c::SECCOMP_RET_ALLOW);
+            assert_eq!(run(&filter, c::AUDIT_ARCH_32, nr), c::SECCOMP_RET_ALLOW);
+        }
+    }
+
+    #[test]
+    fn one_syscall() {
+        let filter = SeccompFilter::new(&[59], &[11]);
+        assert_eq!(filter.prog.len(), 15);
+        assert_eq!(filter.prog[2].k, 4);
+        assert_eq!((filter.prog[4].k, filter.prog[4].jt, filter.prog[4].jf), (59, 0, 1));
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 59), c::SECCOMP_RET_TRACE);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 11), c::SECCOMP_RET_ALLOW);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 0), c::SECCOMP_RET_ALLOW);
+    }
+
+    #[test]
+    fn syscalls() {
+        let nrs: Vec<u64> = (0..100).map(|nr| nr * 3).collect();
+        let filter = SeccompFilter::new(&nrs, &[]);
+        assert_eq!(filter.prog.len(), 5 + nrs.len() * 2 + 5 + 1);
+        assert_eq!(filter.prog[2].k as usize, 2 + nrs.len() * 2);
+        for nr in 0..300 {
+            let ret = if nr % 3 == 0 { c::SECCOMP_RET_TRACE } else { c::SECCOMP_RET_ALLOW };
+            assert_eq!(run(&filter, c::AUDIT_ARCH_64, nr), ret);
+        }
+    }
+
+    #[test]
+    fn compat_arch() {
+        let filter = SeccompFilter::new(&[59, 257], &[11, 295]);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 59), c::SECCOMP_RET_TRACE);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 257), c::SECCOMP_RET_TRACE);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_64, 11), c::SECCOMP_RET_ALLOW);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_32, 11), c::SECCOMP_RET_TRACE);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_32, 295), c::SECCOMP_RET_TRACE);
+        assert_eq!(run(&filter, c::AUDIT_ARCH_32, 59), c::SECCOMP_RET_ALLOW);
+        // unknown arch is allowed
+        assert_eq!(run(&filter, 0, 59), c::SECCOMP_RET_ALLOW);
+    }
+
+    #[test]
+    fn too_many() {
+        let fixed = 11;
+        let nrs: Vec<u64> = (0..((c::BPF_MAXINSNS - fixed) / 2) as u64).collect();
+        let filter = SeccompFilter::new(&nrs, &[]);
+        assert!(filter.prog.len() <= c::BPF_MAXINSNS);
+        assert!(filter.check().is_ok());
+        let nrs: Vec<u64> = (0..((c::BPF_MAXINSNS - fixed) / 2 + 1) as u64).collect();
+        let filter = SeccompFilter::new(&nrs, &[]);
+        assert!(filter.prog.len() > c::BPF_MAXINSNS);
+        assert_eq!(filter.check().unwrap_err().raw_os_error(), Some(c::E2BIG));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::FORMATS;
+
+const CLONE_PIDFD: u64 = 0x00001000;
+const CLONE_VM: u64 = 0x00000100;
+const CLONE_PARENT_SETTID: u64 = 0x00100000;
+const CLONE_CHILD_SETTID: u64 = 0x01000000;
+/// Max nest level of pid namespace, limit of clone_args.set_tid_size
+const MAX_PID_NS_LEVEL: u64 = 32;
+
+const CLONE_FLAG: [(u64, &'static str); 27] = [
+(0x00000100, "CLONE_VM"), (0x00000200, "CLONE_FS"), (0x00000400, "CLONE_FILES"), (0x00000800, "CLONE_SIGHAND"),
+(0x00001000, "CLONE_PIDFD"), (0x00002000, "CLONE_PTRACE"), (0x00004000, "CLONE_VFORK"), (0x00008000, "CLONE_PARENT"),
+(0x00010000, "CLONE_THREAD"), (0x00020000, "CLONE_NEWNS"), (0x00040000, "CLONE_SYSVSEM"), (0x00080000, "CLONE_SETTLS"),
+(0x00100000, "CLONE_PARENT_SETTID"), (0x00200000, "CLONE_CHILD_CLEARTID"), (0x00400000, "CLONE_DETACHED"), (0x00800000, "CLONE_UNTRACED"),
+(0x01000000, "CLONE_CHILD_SETTID"), (0x02000000, "CLONE_NEWCGROUP"), (0x04000000, "CLONE_NEWUTS"), (0x08000000, "CLONE_NEWIPC"),
+(0x10000000, "CLONE_NEWUSER"), (0x20000000, "CLONE_NEWPID"), (0x40000000, "CLONE_NEWNET"), (0x80000000, "CLONE_IO"),
+(0x100000000, "CLONE_CLEAR_SIGHAND"), (0x200000000, "CLONE_INTO_CGROUP"),
+// only for clone3, low byte of clone flags is exit signal
+(0x00000080, "CLONE_NEWTIME"),
+];
+
+/// Write clone flags, low byte is exit signal
+pub fn write_flag(printer: &crate::Printer, value: u64, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let flags = value & !0xff;
+    let sig = value & 0xff;
+    if value == 0 {
+        return printer.write_mask_enum(value, &CLONE_FLAG);
+    }
+    printer.write_flags_begin()?;
+    printer.write_mask_enum_items(flags, &CLONE_FLAG[..CLONE_FLAG.len() - 1], false)?;
+    if sig != 0 {
+        if flags != 0 {
+            printer.write_flags_sep()?;
+        }
+        crate::signal::write_signal(printer, sig, e)?;
+    }
+    printer.write_flags_end()
+}
+
+/// Write parent tid stored by CLONE_PARENT_SETTID or pidfd stored by CLONE_PIDFD of clone
+pub fn write_parent_tid(printer: &crate::Printer, value: u64, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    if e.argn(peek::Arg::ONE) & (CLONE_PARENT_SETTID | CLONE_PIDFD) != 0 {
+        printer.peek_write_number::<types::SInt>(value as types::Ptr, &FORMATS::DEC, pid, e)
+    } else if value == 0 {
+        printer.write_null()
+    } else {
+        printer.write_number_as_pointer(value)
+    }
+}
+
+/// Write child tid stored by CLONE_CHILD_SETTID, it is visible from parent only if memory is shared
+pub fn write_child_tid(printer: &crate::Printer, value: u64, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let flags = e.argn(peek::Arg::ONE);
+    if flags & CLONE_CHILD_SETTID != 0 && flags & CLONE_VM != 0 {
+        printer.peek_write_number::<types::SInt>(value as types::Ptr, &FORMATS::DEC, pid, e)
+    } else if value == 0 {
+        printer.write_null()
+    } else {
+        printer.write_number_as_pointer(value)
+    }
+}
+
+#[repr(C)]#[derive(Default)]#[allow(non_camel_case_types)]
+pub struct clone_args {
+    flags: u64,
+    pidfd: u64,
+    child_tid: u64,
+    parent_tid: u64,
+    exit_signal: u64,
+    stack: u64,
+    stack_size: u64,
+    tls: u64,
+    set_tid: u64,
+    set_tid_size: u64,
+    cgroup: u64,
+}
+
+impl crate::Print for clone_args {
+    fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        printer.write_member("flags")?;
+        printer.write_mask_enum(self.flags, &CLONE_FLAG)?;
+        printer.write_next_member("pidfd")?;
+        printer.write_number_as_pointer(self.pidfd)?;
+        printer.write_next_member("child_tid")?;
+        printer.write_number_as_pointer(self.child_tid)?;
+        printer.write_next_member("parent_tid")?;
+        printer.write_number_as_pointer(self.parent_tid)?;
+        printer.write_next_member("exit_signal")?;
+        crate::signal::write_signal(printer, self.exit_signal, e)?;
+        printer.write_next_member("stack")?;
+        printer.write_number_as_pointer(self.stack)?;
+        printer.write_next_member("stack_size")?;
+        printer.write_number(self.stack_size, &FORMATS::HEX)?;
+        printer.write_next_member("tls")?;
+        printer.write_number_as_pointer(self.tls)?;
+        printer.write_next_member("set_tid")?;
+        if self.set_tid == 0 || self.set_tid_size == 0 || self.set_tid_size > MAX_PID_NS_LEVEL {
+            printer.write_number_as_pointer(self.set_tid)?;
+        } else {
+            printer.peek_write_struct_array::<crate::number::SIntDec>(self.set_tid as types::Ptr, self.set_tid_size as usize, pid, e)?;
+        }
+        printer.write_next_member("set_tid_size")?;
+        printer.write_number(self.set_tid_size, &FORMATS::DEC)?;
+        printer.write_next_member("cgroup")?;
+        printer.write_number(self.cgroup, &FORMATS::DEC)
+    }
+}
+
+/// Write struct clone_args, the struct is extensible so the size passed to clone3 is used
+pub fn write_clone_args(printer: &crate::Printer, buf: &[u8], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let mut args = clone_args::default();
+    let len = std::cmp::min(buf.len(), std::mem::size_of::<clone_args>());
+    unsafe { std::ptr::copy_nonoverlapping(buf.as_ptr(), &mut args as *mut clone_args as *mut u8, len); }
+    printer.write_struct_none_tail(&args, pid, e)
+}
@@ -12,12 +12,12 @@ pub enum TYPES {
     U8(FORMATS), I8(FORMATS), U16(FORMATS), I16(FORMATS), U32(FORMATS), I32(FORMATS), U64(FORMATS), I64(FORMATS),
     INT(FORMATS), UINT(FORMATS), ULONG(FORMATS), LONG(FORMATS), USIZE(FORMATS), SSIZE(FORMATS), PID,
     U64LOW, U64HIGH(FORMATS),
-    AccessatFlag, AtFlag, Clockid, DirFd, EpollctlOp, FdFlag, IoctlArgNoPeek, IoctlReqest, LseekWhence, MadviseAdvice, MmapFlag, MmapProt, NewfstatatFlag, OpenFlag, RenameFlag, RlimitResource, SendFlag, SocketDomain, SocketFlag, SocketType, SocketcallCall,
+    AccessatFlag, AtFlag, Clockid, CloneFlag, DirFd, EpollctlOp, Fd, FdFlag, FutexOp, FutexVal3, IoctlArgNoPeek, IoctlReqest, LseekWhence, MadviseAdvice, MmapFlag, MmapProt, NewfstatatFlag, OpenFlag, RenameFlag, RlimitResource, SendFlag, SigprocmaskHow, Signal, SocketDomain, SocketFlag, SocketType, SocketcallCall,
     IoctlArg, /* maybe peek data */
     PTR,
     IntPtr(FORMATS), I64Ptr(FORMATS), StrPtr, StrPtrLenArgR, ArgsPtr, IntArrayPtrLen2,
-    EpolleventPtr, EpolleventArrayPtrLenArgR, FdsetPtrArg1, IovecPtrLenArg3, IovecPtrLenArg3BufLenArgR, Linuxdirent64PtrLenArgR, MsghdrPtr, MsghdrPtrBufLenArgR,
-    OldoldutsnamePtr, OldutsnamePtr, PollfdPtrLenArg2, Rlimit64Ptr, RlimitPtr, SockaddrPtrLenArg3, SockaddrPtrLenArg3Ptr, SocketcallArgPtr, Statfs64Ptr, StatfsPtr, StatPtr, StatxPtr, SysinfoPtr, TimespecPtr, TimevalPtr, TimexPtr, TimezonePtr, UtsnamePtr,
+    CloneargsPtrLenArg2, CloneChildTidPtr, CloneParentTidPtr, EpolleventPtr, EpolleventArrayPtrLenArgR, FutexTimeout, FutexUaddrPtr, FutexwaitvPtrLenArg2, FdsetPtrArg1, IovecPtrLenArg3, IovecPtrLenArg3BufLenArgR, Linuxdirent64PtrLenArgR, MsghdrPtr, MsghdrPtrBufLenArgR,
+    OldoldutsnamePtr, OldutsnamePtr, PollfdPtrLenArg2, Rlimit64Ptr, RlimitPtr, SigactionPtr, SiginfoPtr, SigsetPtrLenArg2, SigsetPtrLenArg4, SockaddrPtrLenArg3, SockaddrPtrLenArg3Ptr, SocketcallArgPtr, Statfs64Ptr, StatfsPtr, StatPtr, StatxPtr, SysinfoPtr, TimespecPtr, TimevalPtr, TimexPtr, TimezonePtr, UtsnamePtr,
     AsciiOrHexPtrLenArg3, AsciiOrHexPtrLenArgR,
 }
 
@@ -72,6 +72,8 @@ pub struct Config {
 
 impl SyscallPrintConf {
     fn new(conf: CONF, print: &'static [SyscallPrintInfoSet]) -> Self { SyscallPrintConf{conf, print} }
+    pub fn new_skip() -> Self { SyscallPrintConf::new(CONF::SKIP, &SKIPPRINT) }
+    pub fn new_print(nr: NR) -> Self { SyscallPrintConf::new(CONF::PRINT, nr.get_print_info()) }
     pub fn is_simple(&self) -> bool { self.conf == CONF::SIMPLE }
     pub fn is_skip(&self) -> bool { self.conf == CONF::SKIP }
     pub fn is_nopeek(&self) -> bool { self.conf == CONF::NOPEEK }
@@ -81,6 +83,72 @@ impl SyscallPrintConf {
     }
 }
 
+fn invalid_name(msg: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb { prev } else { 1 + prev.min(row[j]).min(cur) };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Syscall names similar to the name, for error message of unknown name
+fn close_names(name: &str) -> Vec<&'static str> {
+    let mut names: Vec<(usize, &'static str)> = arch::sys_uni::map.iter()
+        .map(|(sys, _)| (edit_distance(name, sys), *sys))
+        .filter(|(d, sys)| *d <= std::cmp::max(1, name.len() / 3) || (name.len() > 2 && sys.contains(name)))
+        .collect();
+    names.sort();
+    names.into_iter().take(5).map(|(_, sys)| sys).collect()
+}
+
+fn is_available(nr: NR) -> bool {
+    arch::sys_uni::a64::from_uni(nr).is_some() || arch::sys_uni::a32::from_uni(nr).is_some()
+}
+
+/// Select syscalls by name, class or regex, error if nothing is selected
+pub(crate) fn select_by_name(name: &str) -> std::result::Result<Vec<NR>, std::io::Error> {
+    if let Some(class) = name.strip_prefix('%') {
+        match arch::sys_uni::class_map.iter().find(|(c, _)|{ &class == c }) {
+            Some((_, bits)) => Ok(arch::sys_uni::map.iter().filter(|(_, nr)| nr.class() & bits != 0 && is_available(*nr)).map(|(_, nr)| *nr).collect()),
+            None => {
+                let classes: Vec<String> = arch::sys_uni::class_map.iter().map(|(c, _)| format!("%{}", c)).collect();
+                Err(invalid_name(format!("unknown syscall class: {}, classes are {}", name, classes.join(", "))))
+            },
+        }
+    } else if let Some(pattern) = name.strip_prefix('/').and_then(|n| n.strip_suffix('/')) {
+        let re = crate::regex::Regex::new(pattern).map_err(|e| invalid_name(format!("invalid regex: {}: {}", name, e)))?;
+        let nrs: Vec<NR> = arch::sys_uni::map.iter().filter(|(sys, nr)| re.is_match(sys) && is_available(*nr)).map(|(_, nr)| *nr).collect();
+        if nrs.is_empty() {
+            return Err(invalid_name(format!("no syscall matches: {}", name)));
+        }
+        Ok(nrs)
+    } else {
+        match arch::sys_uni::map.iter().find(|(sys, _)|{ &name == sys }) {
+            Some((_, nr)) if is_available(*nr) => Ok(vec![*nr]),
+            Some(_) => Err(invalid_name(format!("syscall not available on this architecture: {}", name))),
+            None => {
+                let close = close_names(name);
+                if close.is_empty() {
+                    Err(invalid_name(format!("unknown syscall: {}", name)))
+                } else {
+                    Err(invalid_name(format!("unknown syscall: {}, did you mean {}?", name, close.join(", "))))
+                }
+            },
+        }
+    }
+}
+
 impl Config {
     pub fn new() -> Self {
         let conf: Vec<PrintConf> = vec![];
@@ -111,28 +179,20 @@ impl Config {
         self.default = Some(SyscallPrintConf::new(CONF::SKIP, &SKIPPRINT))
     }
 
-    pub fn set_skip_by_name(&mut self, name: &str) {
-        if let Some((_, nr)) = arch::sys_uni::map.iter().find(|(sys, _)|{ &name == sys }) {
-            self.set_conf(*nr, CONF::SKIP);
-        }
+    pub fn set_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
+        self.set_conf_by_name(name, CONF::SKIP)
     }
 
-    pub fn set_not_skip_by_name(&mut self, name: &str) {
-        if let Some((_, nr)) = arch::sys_uni::map.iter().find(|(sys, _)|{ &name == sys }) {
-            self.set_conf(*nr, CONF::PRINT);
-        }
+    pub fn set_not_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
+        self.set_conf_by_name(name, CONF::PRINT)
     }
 
-    pub fn set_simple_by_name(&mut self, name: &str) {
-        if let Some((_, nr)) = arch::sys_uni::map.iter().find(|(sys, _)|{ &name == sys }) {
-            self.set_conf(*nr, CONF::SIMPLE);
-        }
+    pub fn set_simple_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
+        self.set_conf_by_name(name, CONF::SIMPLE)
     }
 
-    pub fn set_nopeek_by_name(&mut self, name: &str) {
-        if let Some((_, nr)) = arch::sys_uni::map.iter().find(|(sys, _)|{ &name == sys }) {
-            self.set_conf(*nr, CONF::NOPEEK);
-        }
+    pub fn set_nopeek_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
+        self.set_conf_by_name(name, CONF::NOPEEK)
     }
 
     pub fn set_skip_by_include_name(&mut self, name: &str) {
@@ -159,6 +219,21 @@ impl Config {
         })
     }
 
+    /// Set config for syscalls selected by the name
+    /// # Arguments
+    /// * `name` - A syscall name, "%class" for syscalls of the class, "/regex/" for syscalls matched names, "!" prefix for syscalls not selected by the rest
+    /// * `conf` - Config set to selected syscalls
+    fn set_conf_by_name(&mut self, name: &str, conf: CONF) -> std::result::Result<(), std::io::Error> {
+        match name.strip_prefix('!') {
+            Some(name) => {
+                let nrs = select_by_name(name)?;
+                arch::sys_uni::map.iter().filter(|(_, nr)| !nrs.contains(nr)).for_each(|(_, nr)| self.set_conf(*nr, conf.clone()));
+            },
+            None => select_by_name(name)?.into_iter().for_each(|nr| self.set_conf(nr, conf.clone())),
+        }
+        Ok(())
+    }
+
     fn set_conf(&mut self, nr: NR, conf: CONF) {
         if let Some(e) = self.conf.iter_mut().find(|x|{x.nr == nr}) {
             e.conf = conf;
@@ -202,8 +277,8 @@ define_syscall_print_info!(UNKNOWN, U64HEX, U64HEX, U64HEX, U64HEX, U64HEX, U64H
 define_syscall_print_info!(SYS_ALIAS_INTDEC_INTDEC, INTDEC, INTDEC);
 define_syscall_print_info!(SYS_ALIAS_INTDEC_INTDEC_INTDEC, INTDEC, INTDEC, INTDEC);
 
-define_syscall_print_info!(ACCEPT, INTDEC, INTDEC, PTR, INTDEC_PTR);
-define_syscall_print_info!(ACCEPT4, INTDEC, INTDEC, PTR, INTDEC_PTR, SocketFlag);
+define_syscall_print_info!(ACCEPT, Fd, Fd, PTR, INTDEC_PTR);
+define_syscall_print_info!(ACCEPT4, Fd, Fd, PTR, INTDEC_PTR, SocketFlag);
 define_syscall_print_info!(ACCESS, INTDEC, StrPtr, INTOCT);
 define_syscall_print_info!(ACCT, INTDEC, StrPtr);
 define_syscall_print_info!(ADD_KEY, LONGDEC, StrPtr, StrPtr, PTR, USIZEDEC, LONGDEC);
@@ -211,7 +286,7 @@ define_syscall_print_info!(ADJTIMEX, INTDEC, PTR);
 define_syscall_print_info!(ALARM, UINTDEC, UINTDEC);
 define_syscall_print_info!(ARCH_PRCTL, LONGDEC, INTDEC, ULONGDEC);
 define_syscall_print_info!(BDFLUSH, INTDEC, INTDEC, LONGDEC);
-define_syscall_print_info!(BIND, INTDEC, INTDEC, SockaddrPtrLenArg3, INTDEC);
+define_syscall_print_info!(BIND, INTDEC, Fd, SockaddrPtrLenArg3, INTDEC);
 define_syscall_print_info!(BPF, INTDEC, INTDEC, PTR, UINTDEC); // TODO implement bpf_attr
 define_syscall_print_info!(BRK, INTDEC, ULONGHEX);
 define_syscall_print_info!(CAPGET, INTDEC, PTR, PTR); // TODO implement args
@@ -224,54 +299,62 @@ define_syscall_print_info!(CLOCK_ADJTIME, INTDEC, Clockid, PTR);
 define_syscall_print_info!(CLOCK_GETTIME, INTDEC, Clockid, PTR);
 define_syscall_print_info!(CLOCK_NANOSLEEP, INTDEC, Clockid, INTDEC, TimespecPtr, PTR);
 define_syscall_print_info!(CLOCK_SETTIME, INTDEC, Clockid, TimespecPtr);
-define_syscall_print_info!(CLONE, LONGDEC, ULONGDEC, PTR, PTR, PTR, PTR);
-define_syscall_print_info!(CLONE3, LONGDEC, PTR, USIZEDEC);
+// x86_64: flags, stack, parent_tid, child_tid, tls
+// i386, arm and aarch64: flags, stack, parent_tid, tls, child_tid
+define_syscall_print_info!(CLONE, LONGDEC, CloneFlag, PTR, PTR, PTR, PTR);
+define_syscall_print_info!(CLONE3, LONGDEC, CloneargsPtrLenArg2, USIZEDEC);
+define_syscall_print_info!(CLOSE, INTDEC, Fd);
 define_syscall_print_info!(CLOSE_RANGE, INTDEC, UINTDEC, UINTDEC, UINTDEC);
-define_syscall_print_info!(COPY_FILE_RANGE, SSIZEDEC, INTDEC, LOFFDEC_PTR, INTDEC, LOFFDEC_PTR, USIZEDEC, UINTDEC);
-define_syscall_print_info!(CREAT, INTDEC, StrPtr, INTOCT);
+define_syscall_print_info!(COPY_FILE_RANGE, SSIZEDEC, Fd, LOFFDEC_PTR, Fd, LOFFDEC_PTR, USIZEDEC, UINTDEC);
+define_syscall_print_info!(CREAT, Fd, StrPtr, INTOCT);
 define_syscall_print_info!(CREATE_MODULE, PTR, StrPtr, USIZEDEC);
 define_syscall_print_info!(DELETE_MODULE, INTDEC, StrPtr, INTDEC);
-define_syscall_print_info!(DUP3, INTDEC, INTDEC, INTDEC, OpenFlag);
-define_syscall_print_info!(EPOLL_CTL, INTDEC, INTDEC, EpollctlOp, INTDEC, EpolleventPtr);
-define_syscall_print_info!(EPOLL_PWAIT, INTDEC, INTDEC, PTR, INTDEC, INTDEC, PTR, USIZEDEC);
-define_syscall_print_info!(EPOLL_WAIT, INTDEC, INTDEC, PTR, INTDEC, INTDEC, PTR);
+define_syscall_print_info!(DUP, Fd, Fd);
+define_syscall_print_info!(DUP2, Fd, Fd, INTDEC);
+define_syscall_print_info!(DUP3, Fd, Fd, INTDEC, OpenFlag);
+define_syscall_print_info!(EPOLL_CTL, INTDEC, Fd, EpollctlOp, Fd, EpolleventPtr);
+define_syscall_print_info!(EPOLL_PWAIT, INTDEC, Fd, PTR, INTDEC, INTDEC, PTR, USIZEDEC);
+define_syscall_print_info!(EPOLL_WAIT, INTDEC, Fd, PTR, INTDEC, INTDEC, PTR);
 define_syscall_print_info!(EXECVE, INTDEC, StrPtr, ArgsPtr, ArgsPtr);
 define_syscall_print_info!(EXECVEAT, INTDEC, DirFd, StrPtr, ArgsPtr, ArgsPtr, AtFlag);
 define_syscall_print_info!(EXIT, NONE, INTDEC);
 define_syscall_print_info!(FACCESSAT, INTDEC, DirFd, StrPtr, INTOCT);
 define_syscall_print_info!(FACCESSAT2, INTDEC, DirFd, StrPtr, INTOCT, AccessatFlag);
-define_syscall_print_info!(FADVISE64, INTDEC, INTDEC, LOFFDEC, USIZEDEC, INTHEX);
+define_syscall_print_info!(FADVISE64, INTDEC, Fd, LOFFDEC, USIZEDEC, INTHEX);
 define_syscall_print_info_bits!(FADVISE64_64, INTDEC,
-                                INTDEC, LOFFDEC, USIZEDEC, INTHEX,
-                                INTDEC, U32HEX, U32HEX, U32HEX, U32HEX, INTHEX);
+                                Fd, LOFFDEC, USIZEDEC, INTHEX,
+                                Fd, U32HEX, U32HEX, U32HEX, U32HEX, INTHEX);
 define_syscall_print_info_bits!(FALLOCATE, INTDEC,
-                                INTDEC, INTDEC, LOFFDEC, LOFFDEC,
-                                INTDEC, INTDEC, U32HEX, U32HEX, U32HEX, U32HEX);
-define_syscall_print_info!(FCHDIR, INTDEC, UINTDEC);
-define_syscall_print_info!(FCHMOD, INTDEC, INTDEC, INTOCT);
+                                Fd, INTDEC, LOFFDEC, LOFFDEC,
+                                Fd, INTDEC, U32HEX, U32HEX, U32HEX, U32HEX);
+define_syscall_print_info!(FCHDIR, INTDEC, Fd);
+define_syscall_print_info!(FCHMOD, INTDEC, Fd, INTOCT);
 define_syscall_print_info!(FCHMODAT, INTDEC, DirFd, StrPtr, INTOCT, AtFlag);
-define_syscall_print_info!(FCHOWN, INTDEC, INTDEC, INTDEC, INTDEC);
+define_syscall_print_info!(FCHOWN, INTDEC, Fd, INTDEC, INTDEC);
 define_syscall_print_info!(FCHOWNAT, INTDEC, DirFd, StrPtr, INTDEC, INTDEC, AtFlag);
-define_syscall_print_info!(FCNTL, INTDEC, UINTDEC, ULONGDEC);
-define_syscall_print_info!(FCNTL64, INTDEC, UINTDEC, ULONGDEC);
-define_syscall_print_info!(FGETXATTR, SSIZEDEC, INTDEC, StrPtr, PTR, USIZEDEC);
-define_syscall_print_info!(FINIT_MODULE, INTDEC, INTDEC, StrPtr, INTHEX);
-define_syscall_print_info!(FSTATFS, INTDEC, UINTDEC, PTR);
-define_syscall_print_info!(FSTATFS64, INTDEC, UINTDEC, USIZEDEC, PTR);
-define_syscall_print_info!(FUTEX, INTDEC, PTR, INTDEC, INTDEC, PTR, PTR, INTDEC);
-define_syscall_print_info!(GETDENTS64, INTDEC, UINTDEC, PTR, UINTDEC);
+define_syscall_print_info!(FCNTL, INTDEC, Fd, UINTDEC, ULONGDEC);
+define_syscall_print_info!(FCNTL64, INTDEC, Fd, UINTDEC, ULONGDEC);
+define_syscall_print_info!(FGETXATTR, SSIZEDEC, Fd, StrPtr, PTR, USIZEDEC);
+define_syscall_print_info!(FINIT_MODULE, INTDEC, Fd, StrPtr, INTHEX);
+define_syscall_print_info!(FSTAT, INTDEC, Fd, PTR);
+define_syscall_print_info!(FSTATFS, INTDEC, Fd, PTR);
+define_syscall_print_info!(FSTATFS64, INTDEC, Fd, USIZEDEC, PTR);
+define_syscall_print_info!(FUTEX, INTDEC, FutexUaddrPtr, FutexOp, INTDEC, FutexTimeout, PTR, FutexVal3);
+define_syscall_print_info!(FUTEX_WAITV, INTDEC, FutexwaitvPtrLenArg2, UINTDEC, UINTHEX, TimespecPtr, Clockid);
+define_syscall_print_info!(GETDENTS64, INTDEC, Fd, PTR, UINTDEC);
 define_syscall_print_info!(GETPID, PID);
 define_syscall_print_info!(GETPGID, PID, PID);
 define_syscall_print_info!(GETRANDOM, SSIZEDEC, PTR, USIZEDEC, INTDEC);
 define_syscall_print_info!(GETTIMEOFDAY, INTDEC, PTR, PTR);
 define_syscall_print_info!(GETXATTR, SSIZEDEC, StrPtr, StrPtr, PTR, USIZEDEC);
 define_syscall_print_info!(INIT_MODULE, INTDEC, PTR, ULONGDEC, StrPtr);
-define_syscall_print_info!(IOCTL, INTDEC, INTDEC, IoctlReqest, IoctlArg);
-define_syscall_print_info!(LSEEK, OFFDEC, INTDEC, OFFDEC, LseekWhence);
+define_syscall_print_info!(IOCTL, INTDEC, Fd, IoctlReqest, IoctlArg);
+define_syscall_print_info!(KILL, INTDEC, PID, Signal);
+define_syscall_print_info!(LSEEK, OFFDEC, Fd, OFFDEC, LseekWhence);
 define_syscall_print_info!(MADIVISE, INTDEC, PTR, INTDEC, MadviseAdvice);
 define_syscall_print_info!(MKDIR, INTDEC, StrPtr, INTOCT);
 define_syscall_print_info!(MKDIRAT, INTDEC, DirFd, StrPtr, INTOCT);
-define_syscall_print_info!(MMAP, PTR, PTR, USIZEDEC, MmapProt, MmapFlag, INTDEC, OFFDEC);
+define_syscall_print_info!(MMAP, PTR, PTR, USIZEDEC, MmapProt, MmapFlag, Fd, OFFDEC);
 define_syscall_print_info!(MOUNT, INTDEC, StrPtr, StrPtr, StrPtr, ULONGDEC, StrPtr); /* TODO mount flag */
 define_syscall_print_info!(MPROTECT, INTDEC, PTR, USIZEDEC, MmapProt);
 define_syscall_print_info!(MUNMAP, INTDEC, PTR, USIZEDEC);
@@ -279,58 +362,70 @@ define_syscall_print_info!(NANOSLEEP, INTDEC, TimespecPtr, PTR);
 define_syscall_print_info!(NEWFSTATAT, INTDEC, DirFd, StrPtr, PTR, NewfstatatFlag);
 define_syscall_print_info!(OLDOLDUNAME, INTDEC, PTR);
 define_syscall_print_info!(OLDUNAME, INTDEC, PTR);
-define_syscall_print_info!(OPEN, INTDEC, StrPtr, OpenFlag, INTOCT);
-define_syscall_print_info!(OPENAT, INTDEC, DirFd, StrPtr, OpenFlag, INTOCT);
-define_syscall_print_info!(OPENAT2, INTDEC, DirFd, StrPtr, PTR, SSIZEDEC);
+define_syscall_print_info!(OPEN, Fd, StrPtr, OpenFlag, INTOCT);
+define_syscall_print_info!(OPENAT, Fd, DirFd, StrPtr, OpenFlag, INTOCT);
+define_syscall_print_info!(OPENAT2, Fd, DirFd, StrPtr, PTR, SSIZEDEC);
 define_syscall_print_info!(PIPE, INTDEC, PTR);
 define_syscall_print_info!(PIPE2, INTDEC, PTR, FdFlag);
 define_syscall_print_info!(POLL, INTDEC, PollfdPtrLenArg2, UINTDEC, INTDEC);
 define_syscall_print_info!(PPOLL, INTDEC, PollfdPtrLenArg2, UINTDEC, TimespecPtr, PTR, SSIZEDEC);
 define_syscall_print_info!(PRLIMIT64, INTDEC, PID, RlimitResource, Rlimit64Ptr, PTR);
 //define_syscall_print_info!(PREAD, SSIZEDEC, UINTDEC, PTR, USIZEDEC, OFFDEC);
-define_syscall_print_info!(PREAD64, SSIZEDEC, UINTDEC, PTR, USIZEDEC, LOFFDEC);
-define_syscall_print_info!(PREADV, SSIZEDEC, UINTDEC, PTR, ULONGDEC, LOFFLOW, LOFFHIGHDEC);
-define_syscall_print_info!(PREADV2, SSIZEDEC, UINTDEC, PTR, ULONGDEC, LOFFLOW, LOFFHIGHDEC, INTHEX);
+define_syscall_print_info!(PREAD64, SSIZEDEC, Fd, PTR, USIZEDEC, LOFFDEC);
+define_syscall_print_info!(PREADV, SSIZEDEC, Fd, PTR, ULONGDEC, LOFFLOW, LOFFHIGHDEC);
+define_syscall_print_info!(PREADV2, SSIZEDEC, Fd, PTR, ULONGDEC, LOFFLOW, LOFFHIGHDEC, INTHEX);
 define_syscall_print_info!(PSELECT, INTDEC, INTDEC, FdsetPtrArg1, FdsetPtrArg1, FdsetPtrArg1, TimespecPtr, PTR);
 //define_syscall_print_info!(PWRITE, SSIZEDEC, INTDEC, AsciiOrHexPtrLenArg3, USIZEDEC, OFFDEC);
-define_syscall_print_info!(PWRITE64, SSIZEDEC, INTDEC, AsciiOrHexPtrLenArg3, USIZEDEC, LOFFDEC);
-define_syscall_print_info!(PWRITEV, SSIZEDEC, INTDEC, AsciiOrHexPtrLenArg3, ULONGDEC, LOFFLOW, LOFFHIGHDEC);
-define_syscall_print_info!(PWRITEV2, SSIZEDEC, INTDEC, AsciiOrHexPtrLenArg3, ULONGDEC, LOFFLOW, LOFFHIGHDEC, INTHEX);
-define_syscall_print_info!(READ, SSIZEDEC, UINTDEC, PTR, USIZEDEC);
+define_syscall_print_info!(PWRITE64, SSIZEDEC, Fd, AsciiOrHexPtrLenArg3, USIZEDEC, LOFFDEC);
+define_syscall_print_info!(PWRITEV, SSIZEDEC, Fd, AsciiOrHexPtrLenArg3, ULONGDEC, LOFFLOW, LOFFHIGHDEC);
+define_syscall_print_info!(PWRITEV2, SSIZEDEC, Fd, AsciiOrHexPtrLenArg3, ULONGDEC, LOFFLOW, LOFFHIGHDEC, INTHEX);
+define_syscall_print_info!(READ, SSIZEDEC, Fd, PTR, USIZEDEC);
 define_syscall_print_info!(READLINK, INTDEC, StrPtr, PTR, USIZEDEC);
-define_syscall_print_info!(READLINKAT, DirFd, INTDEC, StrPtr, PTR, USIZEDEC);
-define_syscall_print_info!(READV, SSIZEDEC, UINTDEC, PTR, INTDEC);
-define_syscall_print_info!(RECVMSG, SSIZEDEC, INTDEC, PTR, SendFlag);
+define_syscall_print_info!(READLINKAT, INTDEC, DirFd, StrPtr, PTR, USIZEDEC);
+define_syscall_print_info!(READV, SSIZEDEC, Fd, PTR, INTDEC);
+define_syscall_print_info!(RECVMSG, SSIZEDEC, Fd, PTR, SendFlag);
 define_syscall_print_info!(RENAME, INTDEC, StrPtr, StrPtr);
 define_syscall_print_info!(RENAMEAT, INTDEC, DirFd, StrPtr, DirFd, StrPtr);
 define_syscall_print_info!(RENAMEAT2, INTDEC, DirFd, StrPtr, DirFd, StrPtr, RenameFlag);
 define_syscall_print_info!(RSEQ, INTDEC, PTR, U32DEC, INTDEC, U32DEC);
-define_syscall_print_info!(RT_SIGACTION, INTDEC, INTDEC, PTR, PTR, USIZEDEC);
+define_syscall_print_info!(RT_SIGACTION, INTDEC, Signal, SigactionPtr, PTR, USIZEDEC);
+define_syscall_print_info!(RT_SIGPENDING, INTDEC, PTR, USIZEDEC);
 define_syscall_print_info!(RT_SIGRETURN, INTDEC, ULONGDEC);
-define_syscall_print_info!(RT_SIGPROCMASK, INTDEC, INTDEC, PTR, PTR, USIZEDEC);
-define_syscall_print_info!(SENDMSG, SSIZEDEC, INTDEC, MsghdrPtr, SendFlag);
+define_syscall_print_info!(RT_SIGPROCMASK, INTDEC, SigprocmaskHow, SigsetPtrLenArg4, PTR, USIZEDEC);
+define_syscall_print_info!(RT_SIGQUEUEINFO, INTDEC, PID, Signal, SiginfoPtr);
+define_syscall_print_info!(RT_SIGSUSPEND, INTDEC, SigsetPtrLenArg2, USIZEDEC);
+define_syscall_print_info!(RT_TGSIGQUEUEINFO, INTDEC, PID, PID, Signal, SiginfoPtr);
+define_syscall_print_info!(SENDMSG, SSIZEDEC, Fd, MsghdrPtr, SendFlag);
 define_syscall_print_info!(SET_ROBUST_LIST, INTDEC, PTR, USIZEDEC);
 define_syscall_print_info!(SET_THREAD_AREA, INTDEC, PTR);
 define_syscall_print_info!(SET_TID_ADDRESS, LONGDEC, PTR);
 define_syscall_print_info!(SETTIMEOFDAY, INTDEC, TimevalPtr, TimezonePtr);
 define_syscall_print_info!(SIGALTSTACK, INTDEC, PTR, PTR);
-define_syscall_print_info!(SOCKET, INTDEC, SocketDomain, SocketType, INTDEC);
+define_syscall_print_info!(SOCKET, Fd, SocketDomain, SocketType, INTDEC);
 define_syscall_print_info!(SOCKETCALL, INTDEC, SocketcallCall, SocketcallArgPtr);
 define_syscall_print_info!(STATFS, INTDEC, StrPtr, PTR);
 define_syscall_print_info!(STATFS64, INTDEC, StrPtr, USIZEDEC, PTR);
-define_syscall_print_info!(STATX, INTDEC, INTDEC, StrPtr, INTDEC, INTDEC, PTR);
+define_syscall_print_info!(STATX, INTDEC, DirFd, StrPtr, INTDEC, INTDEC, PTR);
 define_syscall_print_info!(SYSINFO, INTDEC, PTR);
+define_syscall_print_info!(TGKILL, INTDEC, PID, PID, Signal);
+define_syscall_print_info!(TKILL, INTDEC, PID, Signal);
 define_syscall_print_info!(UGETRLIMIT, INTDEC, RlimitResource, PTR);
 define_syscall_print_info!(UMOUNT2, INTDEC, StrPtr, INTDEC); /* TODO umount flag */
 define_syscall_print_info!(UNAME, INTDEC, PTR);
 define_syscall_print_info!(WAIT4, PID, PID, PTR, INTDEC, PTR);
-define_syscall_print_info!(WRITE, SSIZEDEC, INTDEC, AsciiOrHexPtrLenArg3, USIZEDEC);
-define_syscall_print_info!(WRITEV, SSIZEDEC, UINTDEC, IovecPtrLenArg3, INTDEC);
+define_syscall_print_info!(WRITE, SSIZEDEC, Fd, AsciiOrHexPtrLenArg3, USIZEDEC);
+define_syscall_print_info!(WRITEV, SSIZEDEC, Fd, IovecPtrLenArg3, INTDEC);
 
 define_syscall_print_info_for_ret_args!(RET_ACCEPT, NONE, SockaddrPtrLenArg3Ptr, INTDEC_PTR);
 define_syscall_print_info_for_ret_args!(RET_ADJTIMEX, TimexPtr);
 define_syscall_print_info_for_ret_args!(RET_CLOCK_ADJTIME, NONE, TimexPtr);
 define_syscall_print_info_for_ret_args!(RET_CLOCK_GETTIME, NONE, TimespecPtr);
+#[cfg(target_arch = "x86_64")]
+define_syscall_print_info_bits!(RET_CLONE, NONE,
+                                NONE, NONE, CloneParentTidPtr, CloneChildTidPtr,
+                                NONE, NONE, CloneParentTidPtr, NONE, CloneChildTidPtr, NONE);
+#[cfg(not(target_arch = "x86_64"))]
+define_syscall_print_info_for_ret_args!(RET_CLONE, NONE, NONE, CloneParentTidPtr, NONE, CloneChildTidPtr);
 define_syscall_print_info_for_ret_args!(RET_CLOCK_NANOSLEEP, NONE, NONE, TimespecPtr, TimespecPtr);
 define_syscall_print_info_for_ret_args!(RET_COPY_FILE_RANGE, NONE, LOFFDEC_PTR, NONE, LOFFDEC_PTR);
 define_syscall_print_info_for_ret_args!(RET_EPOLL_WAIT, NONE, EpolleventArrayPtrLenArgR);
@@ -349,6 +444,9 @@ define_syscall_print_info_for_ret_args!(RET_READLINK, NONE, StrPtrLenArgR);
 define_syscall_print_info_for_ret_args!(RET_READLINKAT, NONE, NONE, StrPtrLenArgR);
 define_syscall_print_info_for_ret_args!(RET_READV, NONE, IovecPtrLenArg3BufLenArgR);
 define_syscall_print_info_for_ret_args!(RET_RECVMSG, NONE, MsghdrPtrBufLenArgR);
+define_syscall_print_info_for_ret_args!(RET_RT_SIGACTION, NONE, NONE, SigactionPtr);
+define_syscall_print_info_for_ret_args!(RET_RT_SIGPENDING, SigsetPtrLenArg2);
+define_syscall_print_info_for_ret_args!(RET_RT_SIGPROCMASK, NONE, NONE, SigsetPtrLenArg4);
 define_syscall_print_info_for_ret_args!(RET_PRLIMIT64, NONE, NONE, NONE, Rlimit64Ptr);
 define_syscall_print_info_for_ret_args!(RET_SOCKETCALL, NONE, SocketcallArgPtr);
 define_syscall_print_info_for_ret_args!(RET_STATFS, NONE, StatfsPtr);
@@ -391,14 +489,14 @@ impl SyscallPrinter for NR {
             NR::sys_clock_settime | NR::sys_clock_settime64 => &CLOCK_SETTIME,
             NR::sys_clone => &CLONE,
             NR::sys_clone3 => &CLONE3,
-            NR::sys_close => &SYS_ALIAS_INTDEC_INTDEC,
+            NR::sys_close => &CLOSE,
             NR::sys_close_range => &CLOSE_RANGE,
             NR::sys_copy_file_range => &COPY_FILE_RANGE,
             NR::sys_creat => &CREAT,
             NR::sys_create_module => &CREATE_MODULE,
             NR::sys_delete_module => &DELETE_MODULE,
-            NR::sys_dup => &SYS_ALIAS_INTDEC_INTDEC,
-            NR::sys_dup2 => &SYS_ALIAS_INTDEC_INTDEC_INTDEC,
+            NR::sys_dup => &DUP,
+            NR::sys_dup2 => &DUP2,
             NR::sys_dup3 => &DUP3,
             NR::sys_epoll_create | NR::sys_epoll_create1 => &SYS_ALIAS_INTDEC_INTDEC,
             NR::sys_epoll_ctl => &EPOLL_CTL,
@@ -424,9 +522,11 @@ impl SyscallPrinter for NR {
             NR::sys_fdatasync => &SYS_ALIAS_INTDEC_INTDEC,
             NR::sys_fgetxattr => &FGETXATTR,
             NR::sys_finit_module => &FINIT_MODULE,
+            NR::sys_fstat => &FSTAT,
             NR::sys_fstatfs => &FSTATFS,
             NR::sys_fstatfs64 => &FSTATFS64,
-            NR::sys_futex => &FUTEX,
+            NR::sys_futex | NR::sys_futex_time64 => &FUTEX,
+            NR::sys_futex_waitv => &FUTEX_WAITV,
             NR::sys_getdents64 => &GETDENTS64,
             NR::sys_getegid | NR::sys_getegid32 | NR::sys_geteuid | NR::sys_geteuid32 | NR::sys_getgid | NR::sys_getgid32 | NR::sys_getpgrp
                 | NR::sys_getpid | NR::sys_getppid | NR::sys_gettid | NR::sys_getuid | NR::sys_getuid32 => &GETPID,
@@ -437,6 +537,7 @@ impl SyscallPrinter for NR {
             NR::sys_init_module => &INIT_MODULE,
             NR::sys_ioctl => &IOCTL,
             NR::sys_listen => &SYS_ALIAS_INTDEC_INTDEC_INTDEC,
+            NR::sys_kill => &KILL,
             NR::sys_lseek => &LSEEK,
             NR::sys_madvise => &MADIVISE,
             NR::sys_mkdir => &MKDIR,
@@ -471,8 +572,12 @@ impl SyscallPrinter for NR {
             NR::sys_recvmsg => &RECVMSG,
             NR::sys_rseq => &RSEQ,
             NR::sys_rt_sigaction => &RT_SIGACTION,
+            NR::sys_rt_sigpending => &RT_SIGPENDING,
             NR::sys_rt_sigreturn => &RT_SIGRETURN,
             NR::sys_rt_sigprocmask => &RT_SIGPROCMASK,
+            NR::sys_rt_sigqueueinfo => &RT_SIGQUEUEINFO,
+            NR::sys_rt_sigsuspend => &RT_SIGSUSPEND,
+            NR::sys_rt_tgsigqueueinfo => &RT_TGSIGQUEUEINFO,
             NR::sys_rename => &RENAME,
             NR::sys_renameat => &RENAMEAT,
             NR::sys_renameat2 => &RENAMEAT2,
@@ -488,6 +593,8 @@ impl SyscallPrinter for NR {
             NR::sys_statfs => &STATFS,
             NR::sys_statx => &STATX,
             NR::sys_sysinfo => &SYSINFO,
+            NR::sys_tgkill => &TGKILL,
+            NR::sys_tkill => &TKILL,
             NR::sys_ugetrlimit => &UGETRLIMIT,
             NR::sys_umask => &SYS_ALIAS_INTDEC_INTDEC_INTDEC,
             NR::sys_umount2 => &UMOUNT2,
@@ -505,6 +612,7 @@ impl SyscallPrinter for NR {
             NR::sys_clock_adjtime => &RET_CLOCK_ADJTIME,
             NR::sys_clock_gettime | NR::sys_clock_gettime64 | NR::sys_clock_getres | NR::sys_clock_getres_time64 => &RET_CLOCK_GETTIME,
             NR::sys_clock_nanosleep => &RET_CLOCK_NANOSLEEP,
+            NR::sys_clone => &RET_CLONE,
             NR::sys_copy_file_range => &RET_COPY_FILE_RANGE,
             NR::sys_epoll_wait | NR::sys_epoll_pwait => &RET_EPOLL_WAIT,
             NR::sys_fgetxattr | NR::sys_getxattr | NR::sys_lgetxattr=> &RET_FGETXATTR,
@@ -523,6 +631,9 @@ impl SyscallPrinter for NR {
             NR::sys_readlinkat => &RET_READLINKAT,
             NR::sys_readv | NR::sys_preadv | NR::sys_preadv2 => &RET_READV,
             NR::sys_recvmsg => &RET_RECVMSG,
+            NR::sys_rt_sigaction => &RET_RT_SIGACTION,
+            NR::sys_rt_sigpending => &RET_RT_SIGPENDING,
+            NR::sys_rt_sigprocmask => &RET_RT_SIGPROCMASK,
             NR::sys_socketcall => &RET_SOCKETCALL,
             NR::sys_statfs64 | NR::sys_fstatfs64 => &RET_STATFS64,
             NR::sys_statfs | NR::sys_fstatfs => &RET_STATFS,
@@ -536,3 +647,39 @@ impl SyscallPrinter for NR {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("read", "read"), 0);
+        assert_eq!(edit_distance("", "read"), 4);
+        assert_eq!(edit_distance("read", ""), 4);
+        assert_eq!(edit_distance("raed", "read"), 2);
+        assert_eq!(edit_distance("opn", "open"), 1);
+        assert_eq!(edit_distance("openat", "open"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn close() {
+        assert!(close_names("opn").contains(&"open"));
+        assert!(close_names("mmapp").contains(&"mmap"));
+        assert!(close_names("xyzzyxyzzy").is_empty());
+    }
+
+    #[test]
+    fn select() {
+        assert!(select_by_name("openat").is_ok_and(|nrs| nrs == vec![NR::sys_openat]));
+        assert!(select_by_name("/^openat2?$/").is_ok_and(|nrs| nrs.contains(&NR::sys_openat)));
+        assert!(select_by_name("%file").is_ok_and(|nrs| !nrs.is_empty()));
+        assert!(select_by_name("%nosuchclass").is_err());
+        assert!(select_by_name("/^nosuchsyscall$/").is_err());
+        assert!(select_by_name("/(/").is_err());
+        let err = select_by_name("opn").err().map(|e| e.to_string()).unwrap_or_default();
+        assert!(err.contains("did you mean") && err.contains("open"), "{}", err);
+    }
+}
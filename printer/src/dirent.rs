@@ -15,23 +15,21 @@ macro_rules! offset_of {
 
 impl crate::Print for linux_dirent64 {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".d_ino = ")?;
+        printer.write_member("d_ino")?;
         printer.write_number(self.d_ino, &FORMATS::HEX)?;
-        printer.write(b", .d_off = ")?;
+        printer.write_next_member("d_off")?;
         printer.write_number(self.d_off, &FORMATS::HEX)?;
-        printer.write(b", .d_reclen = ")?;
+        printer.write_next_member("d_reclen")?;
         printer.write_number(self.d_reclen, &FORMATS::DEC)?;
-        printer.write(b", .d_type = ")?;
+        printer.write_next_member("d_type")?;
         printer.write_number_zero_fill(self.d_type)?;
         Ok(())
     }
 
     fn print_flex_tail(&self, printer: &crate::Printer, buf: &[u8], _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b", .d_name = \"")?;
+        printer.write_next_member("d_name")?;
         let n = buf.iter().position(|x| *x == 0).unwrap_or(buf.len());
-        printer.write(&buf[..n])?;
-        printer.write(b"\"")?;
-        Ok(())
+        printer.write_str(&buf[..n])
     }
 
     fn flex_tail_size(&self) -> usize {
@@ -0,0 +1,208 @@
+//! Complete data of read and write syscalls written to files per fd, e.g. `dump/1234.3.in`
+//!
+//! File is per opened fd, `dump/1234.3.in.1` and so on are used for fd or process ID reused in trace.
+use arch::sys_uni::NR;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::Write;
+
+const DIR: &str = "dump";
+
+/// Buffer argument of syscall, always 2nd argument
+#[derive(Clone, Copy)]#[allow(clippy::upper_case_acronyms)]
+enum BUF {
+    /// Buffer pointer
+    PLAIN,
+    /// iovec array pointer, count is 3rd argument
+    IOVEC,
+    /// msghdr pointer
+    MSGHDR,
+}
+
+/// Buffer argument and direction of syscall to dump, true if data is written by tracee
+fn target(nr: NR) -> Option<(BUF, bool)> {
+    match nr {
+        NR::sys_read | NR::sys_pread64 | NR::sys_recv | NR::sys_recvfrom => Some((BUF::PLAIN, false)),
+        NR::sys_write | NR::sys_pwrite64 | NR::sys_send | NR::sys_sendto => Some((BUF::PLAIN, true)),
+        NR::sys_readv | NR::sys_preadv | NR::sys_preadv2 => Some((BUF::IOVEC, false)),
+        NR::sys_writev | NR::sys_pwritev | NR::sys_pwritev2 => Some((BUF::IOVEC, true)),
+        NR::sys_recvmsg => Some((BUF::MSGHDR, false)),
+        NR::sys_sendmsg => Some((BUF::MSGHDR, true)),
+        _ => None,
+    }
+}
+
+/// Dump file and offset of data, or error message if failed to dump
+pub type Dumped = std::result::Result<(String, u64), String>;
+
+/// Fds to dump and files written so far
+pub struct DumpIo {
+    fds: Vec<types::SInt>,
+    /// Glob patterns matched to path of fd
+    globs: Vec<Vec<u8>>,
+    /// Dump file, its path and size per process, fd and direction, removed when fd is closed
+    files: BTreeMap<(types::Pid, types::SInt, bool), (File, String, u64)>,
+    /// Paths created in this trace, not to overwrite by reused fd or process ID
+    created: BTreeSet<String>,
+    /// Dump of syscall in progress
+    done: BTreeMap<types::Pid, Dumped>,
+}
+
+impl DumpIo {
+    pub fn new() -> Self {
+        DumpIo{ fds: vec![], globs: vec![], files: BTreeMap::new(), created: BTreeSet::new(), done: BTreeMap::new() }
+    }
+
+    /// Add fd number, or glob pattern matched to path of fd, e.g. `/var/log/*`, `socket:*`
+    pub fn add(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        if spec.is_empty() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "fd or path glob is required"));
+        }
+        match spec.parse::<types::SInt>() {
+            Ok(fd) if fd >= 0 => self.fds.push(fd),
+            _ => self.globs.push(spec.as_bytes().to_vec()),
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fds.is_empty() && self.globs.is_empty()
+    }
+
+    /// Check whether syscall may be dumped, it shall be stopped at syscall-exit
+    pub fn is_target(&self, nr: NR) -> bool {
+        !self.is_empty() && target(nr).is_some()
+    }
+
+    /// Check whether buffer of syscall is dumped, then buffer argument is output as pointer
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `e` - Syscall summery
+    pub fn is_dumped(&self, pid: types::Pid, e: &peek::SyscallSummery) -> bool {
+        if !self.is_target(e.uni_sysnum()) {
+            return false;
+        }
+        let fd = e.argn(peek::Arg::ONE) as types::SInt;
+        self.fds.contains(&fd)
+            || (!self.globs.is_empty() && peek::get_fd_link(pid, fd).is_ok_and(|l| self.globs.iter().any(|g| crate::glob(g, &l))))
+    }
+
+    /// Dump of syscall in progress, None if not dumped
+    pub fn done(&self, pid: types::Pid) -> Option<&Dumped> {
+        self.done.get(&pid)
+    }
+
+    /// Forget dump of syscall in progress at syscall-exit
+    pub fn exit(&mut self, pid: types::Pid) {
+        self.done.remove(&pid);
+    }
+
+    /// Append data transferred by syscall to dump file of the fd
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `tgid` - Thread group ID of `pid`, fds are shared in thread group
+    /// * `e` - Syscall summery at syscall-exit
+    pub fn dump(&mut self, pid: types::Pid, tgid: types::Pid, e: &peek::SyscallSummery) {
+        let Some((buf, out)) = target(e.uni_sysnum()) else { return };
+        let size = match e.return_value() {
+            Ok(n) if n > 0 && self.is_dumped(pid, e) => n as usize,
+            _ => return,
+        };
+        let fd = e.argn(peek::Arg::ONE) as types::SInt;
+        let r = peek_payload(pid, e, buf, size).and_then(|data| self.append(tgid, fd, out, &data));
+        self.done.insert(pid, r.map_err(|err| err.to_string()));
+    }
+
+    /// Update dump files by change of fds, file of closed or replaced fd is closed
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `tgid` - Thread group ID of `pid`
+    /// * `e` - Syscall summery at syscall-exit
+    pub fn update(&mut self, pid: types::Pid, tgid: types::Pid, e: &peek::SyscallSummery) {
+        if self.is_empty() {
+            return;
+        }
+        match crate::fd::change(pid, e) {
+            crate::fd::CHANGE::CREATED(fds) => self.files.retain(|(p, fd, _), _| *p != tgid || !fds.contains(fd)),
+            crate::fd::CHANGE::CLOSED(first, last) => self.files.retain(|(p, fd, _), _| *p != tgid || *fd < first || *fd > last),
+            crate::fd::CHANGE::EXEC => self.files.retain(|(p, fd, _), _| *p != tgid || peek::get_fd_link(pid, *fd).is_ok()),
+            crate::fd::CHANGE::NONE => {},
+        }
+    }
+
+    /// Close dump files of exited process
+    /// # Arguments
+    /// * `tgid` - A process ID of exited process
+    pub fn close_process(&mut self, tgid: types::Pid) {
+        self.files.retain(|(p, _, _), _| *p != tgid);
+    }
+
+    /// Path of new dump file, suffixed by number if the path is already used in this trace
+    fn new_path(&mut self, tgid: types::Pid, fd: types::SInt, out: bool) -> String {
+        let base = format!("{}/{}.{}.{}", DIR, tgid, fd, if out { "out" } else { "in" });
+        let mut path = base.clone();
+        let mut n = 0;
+        while self.created.contains(&path) {
+            n += 1;
+            path = format!("{}.{}", base, n);
+        }
+        self.created.insert(path.clone());
+        path
+    }
+
+    fn append(&mut self, tgid: types::Pid, fd: types::SInt, out: bool, data: &[u8]) -> std::result::Result<(String, u64), std::io::Error> {
+        let key = (tgid, fd, out);
+        if !self.files.contains_key(&key) {
+            let path = self.new_path(tgid, fd, out);
+            std::fs::create_dir_all(DIR)?;
+            let file = File::create(&path)?;
+            self.files.insert(key, (file, path, 0));
+        }
+        let (file, path, offset) = self.files.get_mut(&key).unwrap();
+        file.write_all(data)?;
+        let at = *offset;
+        *offset += data.len() as u64;
+        Ok((path.clone(), at))
+    }
+}
+
+/// Peek `size` bytes transferred by syscall from buffer argument
+fn peek_payload(pid: types::Pid, e: &peek::SyscallSummery, buf: BUF, size: usize) -> std::result::Result<Vec<u8>, std::io::Error> {
+    let addr = e.argn(peek::Arg::TWO) as types::Ptr;
+    let iov = match buf {
+        BUF::PLAIN => vec![(addr, size)],
+        BUF::IOVEC if e.is_64() => crate::iovec::iovec::peek_array(pid, addr, e.argn(peek::Arg::THR) as usize)?,
+        BUF::IOVEC => crate::iovec::compat_iovec::peek_array(pid, addr, e.argn(peek::Arg::THR) as usize)?,
+        BUF::MSGHDR if e.is_64() => crate::socket::msghdr::peek_iov(pid, addr)?,
+        BUF::MSGHDR => crate::socket::compat_msghdr::peek_iov(pid, addr)?,
+    };
+    let mut data = Vec::with_capacity(size);
+    for (base, len) in iov {
+        let len = std::cmp::min(len, size - data.len());
+        if len == 0 {
+            continue;
+        }
+        let mut chunk = Vec::with_capacity(len);
+        peek::peek_vec(pid, base, &mut chunk, len)?;
+        data.extend_from_slice(&chunk);
+        if data.len() == size {
+            break;
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reused_path() {
+        let mut dump = DumpIo::new();
+        assert_eq!(dump.new_path(100, 3, false), "dump/100.3.in");
+        assert_eq!(dump.new_path(100, 3, true), "dump/100.3.out");
+        assert_eq!(dump.new_path(100, 3, false), "dump/100.3.in.1");
+        assert_eq!(dump.new_path(100, 3, false), "dump/100.3.in.2");
+        assert_eq!(dump.new_path(101, 3, false), "dump/101.3.in");
+    }
+}
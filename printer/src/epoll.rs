@@ -22,8 +22,8 @@ const EVENTS: [(types::UInt, &'static str); 12] = [
 
 impl crate::Print for epoll_event {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".events = ")?; printer.write_mask_enum(self.events, &EVENTS)?;
-        printer.write(b", .data = ")?; printer.write_number(self.data, &FORMATS::HEX)
+        printer.write_member("events")?; printer.write_mask_enum(self.events, &EVENTS)?;
+        printer.write_next_member("data")?; printer.write_number(self.data, &FORMATS::HEX)
     }
 }
 
@@ -32,7 +32,7 @@ const OP: [(types::SInt, &'static str); 3] = [ (1, "EPOLL_CTL_ADD"), (2, "EPOLL_
 pub fn write_op(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
     if value == 0 {
-        printer.write(b"0")
+        printer.write_number(0, &FORMATS::DEC)
     } else {
         printer.write_enum(value as types::SInt, &OP)
     }
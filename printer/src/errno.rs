@@ -3,6 +3,16 @@ pub fn write_errno(printer: &crate::Printer, value: u64, _: &peek::SyscallSummer
     printer.write_enum(value as u32, &ERRNO)
 }
 
+/// Get errno name, None if unknown
+pub fn errno_name(value: u32) -> Option<&'static str> {
+    ERRNO.iter().find(|(v, _)| *v == value).map(|(_, name)| *name)
+}
+
+/// Get errno value from name, None if unknown
+pub fn errno_value(name: &str) -> Option<u32> {
+    ERRNO.iter().find(|(_, n)| *n == name).map(|(value, _)| *value)
+}
+
 const ERRNO: [(u32, &'static str); 149] = [
 (1,"EPERM"),
 (2,"ENOENT"),
@@ -0,0 +1,375 @@
+use crate::FORMATS;
+use arch::sys_uni::NR;
+use std::collections::BTreeMap;
+
+const AF_UNIX: u16 = 1;
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+const AF_NETLINK: u16 = 16;
+const AF_PACKET: u16 = 17;
+const SOCK_STREAM: u64 = 1;
+const SOCK_DGRAM: u64 = 2;
+const SOCK_RAW: u64 = 3;
+const SOCK_TYPE_MASK: u64 = 0xf;
+const F_DUPFD: u64 = 0;
+const F_DUPFD_CLOEXEC: u64 = 1030;
+const CLOSE_RANGE_CLOEXEC: u64 = 4;
+const CLONE_THREAD: u64 = 0x00010000;
+const EINPROGRESS: i32 = 115;
+
+/// Description of fd known from syscall created it
+#[derive(Clone, Default)]
+struct FdInfo {
+    /// Link of /proc/<pid>/fd/<fd> when created, used to check the fd is not replaced by untraced way
+    link: Vec<u8>,
+    proto: Option<&'static str>,
+    local: Option<String>,
+    peer: Option<String>,
+}
+
+impl FdInfo {
+    fn describe(&self) -> Vec<u8> {
+        let mut r = self.link.clone();
+        if let Some(proto) = self.proto {
+            r.push(b' ');
+            r.extend_from_slice(proto.as_bytes());
+        }
+        if self.local.is_some() || self.peer.is_some() {
+            r.push(b' ');
+        }
+        if let Some(local) = &self.local {
+            r.extend_from_slice(local.as_bytes());
+        }
+        if let Some(peer) = &self.peer {
+            r.extend_from_slice(b"->");
+            r.extend_from_slice(peer.as_bytes());
+        }
+        r
+    }
+}
+
+/// Per process fd table tracked from results of syscalls creating, duplicating and closing fd
+pub struct FdTable {
+    table: BTreeMap<types::Pid, BTreeMap<types::SInt, FdInfo>>,
+}
+
+fn readlink(pid: types::Pid, fd: types::SInt) -> Option<Vec<u8>> {
+    peek::get_fd_link(pid, fd).ok()
+}
+
+fn socket_proto(domain: u64, sock_type: u64) -> Option<&'static str> {
+    match (domain as u16, sock_type & SOCK_TYPE_MASK) {
+        (AF_UNIX, _) => Some("UNIX"),
+        (AF_INET, SOCK_STREAM) => Some("TCP"),
+        (AF_INET, SOCK_DGRAM) => Some("UDP"),
+        (AF_INET, SOCK_RAW) => Some("RAW"),
+        (AF_INET6, SOCK_STREAM) => Some("TCPv6"),
+        (AF_INET6, SOCK_DGRAM) => Some("UDPv6"),
+        (AF_INET6, SOCK_RAW) => Some("RAWv6"),
+        (AF_NETLINK, _) => Some("NETLINK"),
+        (AF_PACKET, _) => Some("PACKET"),
+        _ => None,
+    }
+}
+
+/// Format struct sockaddr as address:port or path, None for unsupported family
+fn sockaddr_to_string(buf: &[u8]) -> Option<String> {
+    let family = u16::from_ne_bytes(buf.get(0..2)?.try_into().ok()?);
+    let port = || u16::from_be_bytes(buf[2..4].try_into().unwrap());
+    match family {
+        AF_INET if buf.len() >= 8 => {
+            let addr: [u8; 4] = buf[4..8].try_into().ok()?;
+            Some(format!("{}:{}", std::net::Ipv4Addr::from(addr), port()))
+        },
+        AF_INET6 if buf.len() >= 24 => {
+            let addr: [u8; 16] = buf[8..24].try_into().ok()?;
+            Some(format!("[{}]:{}", std::net::Ipv6Addr::from(addr), port()))
+        },
+        AF_UNIX => {
+            let path = &buf[2..];
+            match path.first() {
+                None => None,
+                // abstract socket name
+                Some(0) => Some(format!("@{}", String::from_utf8_lossy(&path[1..]))),
+                Some(_) => {
+                    let len = path.iter().position(|c| *c == 0).unwrap_or(path.len());
+                    Some(String::from_utf8_lossy(&path[..len]).into_owned())
+                },
+            }
+        },
+        _ => None,
+    }
+}
+
+fn peek_sockaddr(pid: types::Pid, addr: u64, len: usize) -> Option<String> {
+    if addr == 0 || len == 0 {
+        return None;
+    }
+    let len = std::cmp::min(len, 128);
+    let mut buf = Vec::with_capacity(len);
+    peek::peek_vec(pid, addr as types::Ptr, &mut buf, len).ok()?;
+    sockaddr_to_string(&buf)
+}
+
+fn peek_fd_pair(pid: types::Pid, addr: u64) -> Option<[types::SInt; 2]> {
+    peek::peek_data::<[types::SInt; 2]>(pid, addr as types::Ptr).ok()
+}
+
+/// Check whether result of syscall is required to track fd
+pub fn is_tracked(nr: NR) -> bool {
+    matches!(nr,
+        NR::sys_open | NR::sys_openat | NR::sys_openat2 | NR::sys_creat | NR::sys_memfd_create |
+        NR::sys_eventfd | NR::sys_eventfd2 | NR::sys_epoll_create | NR::sys_epoll_create1 |
+        NR::sys_signalfd | NR::sys_signalfd4 | NR::sys_timerfd_create | NR::sys_inotify_init | NR::sys_inotify_init1 |
+        NR::sys_pidfd_open | NR::sys_pidfd_getfd | NR::sys_fanotify_init | NR::sys_userfaultfd | NR::sys_perf_event_open | NR::sys_io_uring_setup |
+        NR::sys_socket | NR::sys_socketpair | NR::sys_pipe | NR::sys_pipe2 | NR::sys_accept | NR::sys_accept4 |
+        NR::sys_connect | NR::sys_bind | NR::sys_dup | NR::sys_dup2 | NR::sys_dup3 | NR::sys_fcntl | NR::sys_fcntl64 |
+        NR::sys_close | NR::sys_close_range | NR::sys_execve | NR::sys_execveat |
+        NR::sys_fork | NR::sys_vfork | NR::sys_clone | NR::sys_clone3)
+}
+
+/// Change of fds by succeeded syscall
+#[allow(clippy::upper_case_acronyms)]
+pub enum CHANGE {
+    NONE,
+    /// New fds, an existing fd of the number is replaced, e.g. by dup2
+    CREATED(Vec<types::SInt>),
+    /// Fds from first to last are closed
+    CLOSED(types::SInt, types::SInt),
+    /// Fds with close-on-exec are closed
+    EXEC,
+}
+
+/// Get change of fds by result of syscall
+/// # Arguments
+/// * `pid` - A thread ID called syscall
+/// * `e` - Syscall summery at syscall-exit
+pub fn change(pid: types::Pid, e: &peek::SyscallSummery) -> CHANGE {
+    let arg = |n: peek::Arg| e.argn(n);
+    let Ok(ret) = e.return_value() else { return CHANGE::NONE };
+    let pair = |addr: u64| peek_fd_pair(pid, addr).map_or(CHANGE::NONE, |p| CHANGE::CREATED(p.to_vec()));
+    match e.uni_sysnum() {
+        NR::sys_socketpair => pair(arg(peek::Arg::FUR)),
+        NR::sys_pipe | NR::sys_pipe2 => pair(arg(peek::Arg::ONE)),
+        NR::sys_fcntl | NR::sys_fcntl64 if arg(peek::Arg::TWO) == F_DUPFD || arg(peek::Arg::TWO) == F_DUPFD_CLOEXEC => CHANGE::CREATED(vec![ret as types::SInt]),
+        NR::sys_close => CHANGE::CLOSED(arg(peek::Arg::ONE) as types::SInt, arg(peek::Arg::ONE) as types::SInt),
+        NR::sys_close_range if arg(peek::Arg::THR) & CLOSE_RANGE_CLOEXEC == 0 => {
+            CHANGE::CLOSED(arg(peek::Arg::ONE) as u32 as types::SInt, std::cmp::min(arg(peek::Arg::TWO) as u32, types::SInt::MAX as u32) as types::SInt)
+        },
+        NR::sys_execve | NR::sys_execveat => CHANGE::EXEC,
+        NR::sys_fcntl | NR::sys_fcntl64 | NR::sys_close_range | NR::sys_connect | NR::sys_bind |
+        NR::sys_fork | NR::sys_vfork | NR::sys_clone | NR::sys_clone3 => CHANGE::NONE,
+        nr if is_tracked(nr) => CHANGE::CREATED(vec![ret as types::SInt]),
+        _ => CHANGE::NONE,
+    }
+}
+
+impl FdTable {
+    pub fn new() -> Self {
+        FdTable{ table: BTreeMap::new() }
+    }
+
+    fn fds(&mut self, tgid: types::Pid) -> &mut BTreeMap<types::SInt, FdInfo> {
+        self.table.entry(tgid).or_default()
+    }
+
+    fn get(&self, tgid: types::Pid, fd: types::SInt) -> Option<&FdInfo> {
+        self.table.get(&tgid).and_then(|t| t.get(&fd))
+    }
+
+    fn add(&mut self, pid: types::Pid, tgid: types::Pid, fd: types::SInt, info: FdInfo) {
+        if let Some(link) = readlink(pid, fd) {
+            self.fds(tgid).insert(fd, FdInfo{ link, ..info });
+        }
+    }
+
+    fn dup(&mut self, pid: types::Pid, tgid: types::Pid, old: types::SInt, new: types::SInt) {
+        let info = self.get(tgid, old).cloned().unwrap_or_default();
+        self.add(pid, tgid, new, info);
+    }
+
+    fn remove(&mut self, tgid: types::Pid, fd: types::SInt) {
+        self.fds(tgid).remove(&fd);
+    }
+
+    /// Get description of fd, the link in /proc is used if fd is opened before trace or by untraced way
+    pub fn describe(&self, pid: types::Pid, tgid: types::Pid, fd: types::SInt) -> Option<Vec<u8>> {
+        match (readlink(pid, fd), self.get(tgid, fd)) {
+            (Some(link), Some(info)) if link == info.link => Some(info.describe()),
+            (Some(link), _) => Some(link),
+            // e.g. the fd is already closed at syscall-exit of close
+            (None, Some(info)) => Some(info.describe()),
+            (None, None) => None,
+        }
+    }
+
+    /// Forget fd table of exited process
+    /// # Arguments
+    /// * `tgid` - A process ID of exited process
+    pub fn exit(&mut self, tgid: types::Pid) {
+        self.table.remove(&tgid);
+    }
+
+    /// Update fd table by result of syscall
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `tgid` - A process ID of the thread
+    /// * `e` - Syscall summery at syscall-exit
+    pub fn update(&mut self, pid: types::Pid, tgid: types::Pid, e: &peek::SyscallSummery) {
+        let arg = |n: peek::Arg| e.argn(n);
+        let ret = match e.return_value() {
+            Ok(r) => r as types::SInt,
+            Err(err) => {
+                // non-blocking connect is still in progress, but the peer is fixed
+                if e.uni_sysnum() == NR::sys_connect && err.raw_os_error().map(i32::wrapping_abs) == Some(EINPROGRESS) {
+                    let peer = peek_sockaddr(pid, arg(peek::Arg::TWO), arg(peek::Arg::THR) as usize);
+                    if let Some(info) = self.fds(tgid).get_mut(&(arg(peek::Arg::ONE) as types::SInt)) {
+                        info.peer = peer;
+                    }
+                }
+                return;
+            },
+        };
+        match e.uni_sysnum() {
+            NR::sys_socket => {
+                let proto = socket_proto(arg(peek::Arg::ONE), arg(peek::Arg::TWO));
+                self.add(pid, tgid, ret, FdInfo{ proto, ..FdInfo::default() });
+            },
+            NR::sys_socketpair => {
+                let proto = socket_proto(arg(peek::Arg::ONE), arg(peek::Arg::TWO));
+                if let Some(pair) = peek_fd_pair(pid, arg(peek::Arg::FUR)) {
+                    pair.iter().for_each(|fd| self.add(pid, tgid, *fd, FdInfo{ proto, ..FdInfo::default() }));
+                }
+            },
+            NR::sys_pipe | NR::sys_pipe2 => {
+                if let Some(pair) = peek_fd_pair(pid, arg(peek::Arg::ONE)) {
+                    pair.iter().for_each(|fd| self.add(pid, tgid, *fd, FdInfo::default()));
+                }
+            },
+            NR::sys_accept | NR::sys_accept4 => {
+                let listen = self.get(tgid, arg(peek::Arg::ONE) as types::SInt).cloned().unwrap_or_default();
+                let addrlen = arg(peek::Arg::THR);
+                let len = if addrlen == 0 { 0 } else { peek::peek_data::<types::SInt>(pid, addrlen as types::Ptr).unwrap_or(0) as usize };
+                let peer = peek_sockaddr(pid, arg(peek::Arg::TWO), len);
+                self.add(pid, tgid, ret, FdInfo{ proto: listen.proto, local: listen.local, peer, ..FdInfo::default() });
+            },
+            NR::sys_connect => {
+                let peer = peek_sockaddr(pid, arg(peek::Arg::TWO), arg(peek::Arg::THR) as usize);
+                if let Some(info) = self.fds(tgid).get_mut(&(arg(peek::Arg::ONE) as types::SInt)) {
+                    info.peer = peer;
+                }
+            },
+            NR::sys_bind => {
+                let local = peek_sockaddr(pid, arg(peek::Arg::TWO), arg(peek::Arg::THR) as usize);
+                if let Some(info) = self.fds(tgid).get_mut(&(arg(peek::Arg::ONE) as types::SInt)) {
+                    info.local = local;
+                }
+            },
+            NR::sys_dup | NR::sys_dup2 | NR::sys_dup3 => {
+                self.dup(pid, tgid, arg(peek::Arg::ONE) as types::SInt, ret);
+            },
+            NR::sys_fcntl | NR::sys_fcntl64 => {
+                if arg(peek::Arg::TWO) == F_DUPFD || arg(peek::Arg::TWO) == F_DUPFD_CLOEXEC {
+                    self.dup(pid, tgid, arg(peek::Arg::ONE) as types::SInt, ret);
+                }
+            },
+            NR::sys_close => {
+                self.remove(tgid, arg(peek::Arg::ONE) as types::SInt);
+            },
+            NR::sys_close_range => {
+                if arg(peek::Arg::THR) & CLOSE_RANGE_CLOEXEC == 0 {
+                    let (first, last) = (arg(peek::Arg::ONE) as u32, arg(peek::Arg::TWO) as u32);
+                    self.fds(tgid).retain(|fd, _| (*fd as u32) < first || (*fd as u32) > last);
+                }
+            },
+            NR::sys_execve | NR::sys_execveat => {
+                // fds with close-on-exec are closed
+                self.fds(tgid).retain(|fd, info| readlink(pid, *fd).as_ref() == Some(&info.link));
+            },
+            NR::sys_fork | NR::sys_vfork | NR::sys_clone | NR::sys_clone3 => {
+                let flags = match e.uni_sysnum() {
+                    NR::sys_clone => arg(peek::Arg::ONE),
+                    NR::sys_clone3 => peek::peek_data::<u64>(pid, arg(peek::Arg::ONE) as types::Ptr).unwrap_or(0),
+                    _ => 0,
+                };
+                if ret > 0 && flags & CLONE_THREAD == 0 {
+                    let fds = self.fds(tgid).clone();
+                    self.table.insert(ret, fds);
+                }
+            },
+            nr if is_tracked(nr) => {
+                self.add(pid, tgid, ret, FdInfo::default());
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Write fd with path or socket description if fd path annotation is enabled
+pub fn write_fd(printer: &crate::Printer, value: u64, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let fd = value as types::SInt;
+    let desc = if printer.opts.fd_path && fd >= 0 {
+        printer.fds.borrow().describe(pid, printer.get_tgid(pid), fd)
+    } else {
+        None
+    };
+    match desc {
+        None => printer.write_number(fd, &FORMATS::DEC),
+        Some(desc) if printer.is_json() => {
+            printer.write(b"{")?;
+            printer.write_member("fd")?;
+            printer.write_number(fd, &FORMATS::DEC)?;
+            printer.write_next_member("path")?;
+            printer.write_str(&desc)?;
+            printer.write(b"}")
+        },
+        Some(desc) => {
+            printer.write_number(fd, &FORMATS::DEC)?;
+            printer.write(b"<")?;
+            printer.write_graph_ascii_or_hex(&desc)?;
+            printer.write(b">")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sockaddr(family: u16, tail: &[u8]) -> Vec<u8> {
+        [&family.to_ne_bytes()[..], tail].concat()
+    }
+
+    #[test]
+    fn sockaddr_inet() {
+        let buf = sockaddr(AF_INET, &[0x1f, 0x90, 127, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(sockaddr_to_string(&buf).as_deref(), Some("127.0.0.1:8080"));
+        assert_eq!(sockaddr_to_string(&buf[..6]), None);
+    }
+
+    #[test]
+    fn sockaddr_inet6() {
+        let mut tail = vec![0x00, 0x35, 0, 0, 0, 0];
+        tail.extend_from_slice(&std::net::Ipv6Addr::LOCALHOST.octets());
+        tail.extend_from_slice(&[0, 0, 0, 0]);
+        let buf = sockaddr(AF_INET6, &tail);
+        assert_eq!(sockaddr_to_string(&buf).as_deref(), Some("[::1]:53"));
+        assert_eq!(sockaddr_to_string(&buf[..20]), None);
+    }
+
+    #[test]
+    fn sockaddr_unix() {
+        assert_eq!(sockaddr_to_string(&sockaddr(AF_UNIX, b"/run/sock\0\0\0")).as_deref(), Some("/run/sock"));
+        assert_eq!(sockaddr_to_string(&sockaddr(AF_UNIX, b"/run/sock")).as_deref(), Some("/run/sock"));
+        assert_eq!(sockaddr_to_string(&sockaddr(AF_UNIX, b"\0name")).as_deref(), Some("@name"));
+        // unnamed socket
+        assert_eq!(sockaddr_to_string(&sockaddr(AF_UNIX, b"")), None);
+    }
+
+    #[test]
+    fn sockaddr_unsupported() {
+        assert_eq!(sockaddr_to_string(&[]), None);
+        assert_eq!(sockaddr_to_string(&[1]), None);
+        assert_eq!(sockaddr_to_string(&sockaddr(AF_NETLINK, &[0; 10])), None);
+    }
+}
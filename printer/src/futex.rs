@@ -0,0 +1,157 @@
+use crate::FORMATS;
+use arch::sys_uni::NR;
+
+const FUTEX_WAIT: u32 = 0;
+const FUTEX_REQUEUE: u32 = 3;
+const FUTEX_CMP_REQUEUE: u32 = 4;
+const FUTEX_WAKE_OP: u32 = 5;
+const FUTEX_LOCK_PI: u32 = 6;
+const FUTEX_WAIT_BITSET: u32 = 9;
+const FUTEX_WAKE_BITSET: u32 = 10;
+const FUTEX_WAIT_REQUEUE_PI: u32 = 11;
+const FUTEX_CMP_REQUEUE_PI: u32 = 12;
+const FUTEX_LOCK_PI2: u32 = 13;
+const FUTEX_CMD_MASK: u32 = !(0x80 | 0x100);
+const FUTEX_BITSET_MATCH_ANY: u32 = 0xffffffff;
+
+const FUTEX_CMD: [(u32, &'static str); 14] = [
+(0, "FUTEX_WAIT"), (1, "FUTEX_WAKE"), (2, "FUTEX_FD"), (3, "FUTEX_REQUEUE"), (4, "FUTEX_CMP_REQUEUE"),
+(5, "FUTEX_WAKE_OP"), (6, "FUTEX_LOCK_PI"), (7, "FUTEX_UNLOCK_PI"), (8, "FUTEX_TRYLOCK_PI"), (9, "FUTEX_WAIT_BITSET"),
+(10, "FUTEX_WAKE_BITSET"), (11, "FUTEX_WAIT_REQUEUE_PI"), (12, "FUTEX_CMP_REQUEUE_PI"), (13, "FUTEX_LOCK_PI2"),
+];
+const FUTEX_FLAG: [(u32, &'static str); 2] = [ (0x80, "FUTEX_PRIVATE_FLAG"), (0x100, "FUTEX_CLOCK_REALTIME"), ];
+
+const FUTEX_OP: [(u32, &'static str); 5] = [
+(0, "FUTEX_OP_SET"), (1, "FUTEX_OP_ADD"), (2, "FUTEX_OP_OR"), (3, "FUTEX_OP_ANDN"), (4, "FUTEX_OP_XOR"),
+];
+const FUTEX_OP_OPARG_SHIFT: u32 = 8;
+const FUTEX_OP_CMP: [(u32, &'static str); 6] = [
+(0, "FUTEX_OP_CMP_EQ"), (1, "FUTEX_OP_CMP_NE"), (2, "FUTEX_OP_CMP_LT"), (3, "FUTEX_OP_CMP_LE"), (4, "FUTEX_OP_CMP_GT"), (5, "FUTEX_OP_CMP_GE"),
+];
+
+const FUTEX2_SIZE: [(u32, &'static str); 4] = [ (0, "FUTEX2_SIZE_U8"), (1, "FUTEX2_SIZE_U16"), (2, "FUTEX2_SIZE_U32"), (3, "FUTEX2_SIZE_U64"), ];
+const FUTEX2_FLAG: [(u32, &'static str); 3] = [ (0x04, "FUTEX2_NUMA"), (0x08, "FUTEX2_MPOL"), (0x80, "FUTEX2_PRIVATE"), ];
+
+fn futex_cmd(e: &peek::SyscallSummery) -> u32 {
+    e.argn(peek::Arg::TWO) as u32 & FUTEX_CMD_MASK
+}
+
+/// Write futex operation and flags
+pub fn write_op(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let value = value as u32;
+    printer.write_flags_begin()?;
+    printer.write_enum(value & FUTEX_CMD_MASK, &FUTEX_CMD)?;
+    printer.write_mask_enum_items(value & !FUTEX_CMD_MASK, &FUTEX_FLAG, true)?;
+    printer.write_flags_end()
+}
+
+/// Write futex word address with current value of the word
+pub fn write_uaddr(printer: &crate::Printer, value: u64, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    if value == 0 {
+        return printer.write_null();
+    }
+    let word = peek::peek_data::<u32>(pid, value as types::Ptr)?;
+    printer.write(b"{")?;
+    printer.write_member("uaddr")?;
+    printer.write_number_as_pointer(value)?;
+    printer.write_next_member("val")?;
+    printer.write_number(word, &FORMATS::DEC)?;
+    printer.write(b"}")
+}
+
+/// Write timeout argument, it is used as val2 by some operations
+pub fn write_timeout(printer: &crate::Printer, value: u64, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    match futex_cmd(e) {
+        FUTEX_WAIT | FUTEX_LOCK_PI | FUTEX_LOCK_PI2 | FUTEX_WAIT_BITSET | FUTEX_WAIT_REQUEUE_PI => {
+            if e.uni_sysnum() == NR::sys_futex && !e.is_64() {
+                printer.peek_write_struct::<crate::time::compat_timespec>(value as types::Ptr, pid, e)
+            } else {
+                printer.peek_write_struct::<crate::time::kernel_timespec>(value as types::Ptr, pid, e)
+            }
+        },
+        FUTEX_REQUEUE | FUTEX_CMP_REQUEUE | FUTEX_WAKE_OP | FUTEX_CMP_REQUEUE_PI => {
+            printer.write_number(value as u32, &FORMATS::DEC)
+        },
+        _ => printer.write_number_as_pointer(value),
+    }
+}
+
+/// Sign extend 12 bit field of FUTEX_WAKE_OP like kernel `sign_extend32(v, 11)`
+fn sign_extend12(value: u32) -> i32 {
+    ((value << 20) as i32) >> 20
+}
+
+/// Write encoded operation of FUTEX_WAKE_OP
+fn write_wake_op(printer: &crate::Printer, value: u32) -> std::result::Result<(), std::io::Error> {
+    let op = (value >> 28) & 0x7;
+    let shift = (value >> 28) & FUTEX_OP_OPARG_SHIFT;
+    printer.write(b"{")?;
+    printer.write_member("op")?;
+    printer.write_flags_begin()?;
+    printer.write_enum(op, &FUTEX_OP)?;
+    if shift != 0 {
+        printer.write_flags_sep()?;
+        printer.write_text(b"FUTEX_OP_OPARG_SHIFT")?;
+    }
+    printer.write_flags_end()?;
+    printer.write_next_member("oparg")?;
+    printer.write_number(sign_extend12(value >> 12), &FORMATS::DEC)?;
+    printer.write_next_member("cmp")?;
+    printer.write_enum((value >> 24) & 0xf, &FUTEX_OP_CMP)?;
+    printer.write_next_member("cmparg")?;
+    printer.write_number(sign_extend12(value), &FORMATS::DEC)?;
+    printer.write(b"}")
+}
+
+/// Write val3 argument, the meaning depends on operation
+pub fn write_val3(printer: &crate::Printer, value: u64, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let value = value as u32;
+    match futex_cmd(e) {
+        FUTEX_WAKE_OP => write_wake_op(printer, value),
+        FUTEX_WAIT_BITSET | FUTEX_WAKE_BITSET if value == FUTEX_BITSET_MATCH_ANY => printer.write_text(b"FUTEX_BITSET_MATCH_ANY"),
+        FUTEX_WAIT_BITSET | FUTEX_WAKE_BITSET => printer.write_number(value, &FORMATS::HEX),
+        _ => printer.write_number(value, &FORMATS::DEC),
+    }
+}
+
+fn write_futex2_flags(printer: &crate::Printer, value: u32) -> std::result::Result<(), std::io::Error> {
+    printer.write_flags_begin()?;
+    printer.write_enum(value & 0x3, &FUTEX2_SIZE)?;
+    printer.write_mask_enum_items(value & !0x3, &FUTEX2_FLAG, true)?;
+    printer.write_flags_end()
+}
+
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct futex_waitv {
+    val: u64,
+    uaddr: u64,
+    flags: u32,
+    __reserved: u32,
+}
+
+impl crate::Print for futex_waitv {
+    fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        printer.write_member("val")?;
+        printer.write_number(self.val, &FORMATS::DEC)?;
+        printer.write_next_member("uaddr")?;
+        if write_uaddr(printer, self.uaddr, pid, e).is_err() {
+            printer.write_number_as_pointer(self.uaddr)?;
+        }
+        printer.write_next_member("flags")?;
+        write_futex2_flags(printer, self.flags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_op_arg() {
+        assert_eq!(sign_extend12(0), 0);
+        assert_eq!(sign_extend12(0x7ff), 2047);
+        assert_eq!(sign_extend12(0x800), -2048);
+        assert_eq!(sign_extend12(0xfff), -1);
+        assert_eq!(sign_extend12(0x1fff001 >> 12), -1);
+    }
+}
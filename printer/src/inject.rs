@@ -0,0 +1,219 @@
+//! Syscall fault injection rules, e.g. `openat:error=ENOSPC:when=3+`
+use arch::sys_uni::NR;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+fn invalid_spec(msg: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg)
+}
+
+/// Syscall invocation counts to inject, `first[..last][+[step]]`, counted from 1 per thread and syscall
+#[derive(Clone, Copy)]
+struct When {
+    first: u64,
+    last: Option<u64>,
+    step: u64,
+}
+
+impl When {
+    const ALWAYS: When = When{ first: 1, last: None, step: 1 };
+
+    fn parse(s: &str) -> Option<Self> {
+        let (range, step) = match s.split_once('+') {
+            Some((r, "")) => (r, Some(1)),
+            Some((r, step)) => (r, Some(step.parse().ok()?)),
+            None => (s, None),
+        };
+        let (first, last) = match range.split_once("..") {
+            Some((f, l)) => (f.parse().ok()?, Some(l.parse().ok()?)),
+            // without range nor step, only the count
+            None => (range.parse().ok()?, None),
+        };
+        let last = if last.is_none() && step.is_none() { Some(first) } else { last };
+        let step = step.unwrap_or(1);
+        if first == 0 || step == 0 || last.is_some_and(|l| l < first) {
+            return None;
+        }
+        Some(When{ first, last, step })
+    }
+
+    fn is_match(&self, count: u64) -> bool {
+        count >= self.first && self.last.map_or(true, |l| count <= l) && (count - self.first) % self.step == 0
+    }
+}
+
+/// Result forced to syscall, the syscall is not executed
+#[derive(Clone, Copy, PartialEq)]
+pub enum FAULT {
+    NONE,
+    ERROR(i32),
+    RETVAL(i64),
+}
+
+/// Injection applied to syscall in progress
+#[derive(Clone, Copy)]
+struct Injected {
+    fault: FAULT,
+    delay_enter: Option<Duration>,
+    delay_exit: Option<Duration>,
+}
+
+struct Rule {
+    nrs: Vec<NR>,
+    injected: Injected,
+    when: When,
+}
+
+/// Parse delay, number with suffix `s`, `ms`, `us` or `ns`, microseconds without suffix
+fn parse_delay(s: &str) -> Option<Duration> {
+    let (num, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let num = num.parse::<u64>().ok()?;
+    match unit {
+        "s" => Some(Duration::from_secs(num)),
+        "ms" => Some(Duration::from_millis(num)),
+        "us" | "" => Some(Duration::from_micros(num)),
+        "ns" => Some(Duration::from_nanos(num)),
+        _ => None,
+    }
+}
+
+/// Injection rules and invocation counts
+pub struct Inject {
+    rules: Vec<Rule>,
+    /// Invocation counts per thread and syscall, NR as u32
+    counts: BTreeMap<(types::Pid, u32), u64>,
+    /// Injection to syscall in progress
+    injected: BTreeMap<types::Pid, Injected>,
+}
+
+impl Inject {
+    pub fn new() -> Self {
+        Inject{ rules: vec![], counts: BTreeMap::new(), injected: BTreeMap::new() }
+    }
+
+    /// Add rule by `SET:OPTION[:OPTION...]`, OPTION is `error=ERRNO`, `retval=N`, `delay_enter=T`, `delay_exit=T` or `when=EXPR`
+    /// # Arguments
+    /// * `spec` - Syscall names separated comma, then options separated colon or comma
+    pub fn add(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        let (set, opts) = spec.split_once(':').unwrap_or((spec, ""));
+        let mut nrs = vec![];
+        for name in set.split(',') {
+            nrs.append(&mut crate::config::select_by_name(name)?);
+        }
+        let mut injected = Injected{ fault: FAULT::NONE, delay_enter: None, delay_exit: None };
+        let mut when = When::ALWAYS;
+        for item in opts.split([':', ',']).filter(|i| !i.is_empty()) {
+            let invalid = |v: &str| invalid_spec(format!("invalid inject option: {}", v));
+            match item.split_once('=') {
+                Some(("error", v)) => {
+                    let e = crate::errno::errno_value(v).or_else(|| v.parse::<u32>().ok()).filter(|e| *e > 0 && *e < 4096);
+                    injected.fault = FAULT::ERROR(e.ok_or_else(|| invalid_spec(format!("unknown errno: {}", v)))? as i32);
+                },
+                Some(("retval", v)) => injected.fault = FAULT::RETVAL(v.parse().map_err(|_| invalid(item))?),
+                Some(("delay_enter", v)) => injected.delay_enter = Some(parse_delay(v).ok_or_else(|| invalid(item))?),
+                Some(("delay_exit", v)) => injected.delay_exit = Some(parse_delay(v).ok_or_else(|| invalid(item))?),
+                Some(("when", v)) => when = When::parse(v).ok_or_else(|| invalid_spec(format!("invalid when: {}", v)))?,
+                _ => return Err(invalid_spec(format!("unknown inject option: {}", item))),
+            }
+        }
+        if injected.fault == FAULT::NONE && injected.delay_enter.is_none() && injected.delay_exit.is_none() {
+            return Err(invalid_spec(format!("error, retval, delay_enter or delay_exit is required: {}", spec)));
+        }
+        self.rules.push(Rule{ nrs, injected, when });
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check whether syscall has rule, it shall be stopped by seccomp filter
+    pub fn contains(&self, nr: NR) -> bool {
+        self.rules.iter().any(|r| r.nrs.contains(&nr))
+    }
+
+    /// Count invocation at syscall-entry, return true if injection is applied, the first matched rule is applied
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `nr` - Called syscall
+    pub fn entry(&mut self, pid: types::Pid, nr: NR) -> bool {
+        // syscall-exit of former syscall may be lost, e.g. by detach
+        self.injected.remove(&pid);
+        let Some(rule) = self.rules.iter().find(|r| r.nrs.contains(&nr)) else { return false };
+        let count = self.counts.entry((pid, nr as u32)).or_insert(0);
+        *count += 1;
+        if !rule.when.is_match(*count) {
+            return false;
+        }
+        self.injected.insert(pid, rule.injected);
+        true
+    }
+
+    /// Result forced to syscall in progress of the thread
+    pub fn fault(&self, pid: types::Pid) -> FAULT {
+        self.injected.get(&pid).map_or(FAULT::NONE, |i| i.fault)
+    }
+
+    /// Delay at syscall-entry or syscall-exit of syscall in progress of the thread
+    pub fn delay(&self, pid: types::Pid, entry: bool) -> Option<Duration> {
+        let i = self.injected.get(&pid)?;
+        if entry { i.delay_enter } else { i.delay_exit }
+    }
+
+    /// Syscall in progress is finished
+    pub fn exit(&mut self, pid: types::Pid) {
+        self.injected.remove(&pid);
+    }
+
+    /// Forget counts of exited thread
+    pub fn exit_process(&mut self, pid: types::Pid) {
+        self.injected.remove(&pid);
+        self.counts.retain(|(p, _), _| *p != pid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(s: &str) -> Vec<u64> {
+        let when = When::parse(s).unwrap();
+        (1..=10).filter(|c| when.is_match(*c)).collect()
+    }
+
+    #[test]
+    fn when_count() {
+        assert_eq!(matched("3"), vec![3]);
+        assert_eq!(matched("1"), vec![1]);
+    }
+
+    #[test]
+    fn when_range_step() {
+        assert_eq!(matched("3+"), vec![3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(matched("2+3"), vec![2, 5, 8]);
+        assert_eq!(matched("2..5"), vec![2, 3, 4, 5]);
+        assert_eq!(matched("2..8+3"), vec![2, 5, 8]);
+        assert_eq!(matched("4..4"), vec![4]);
+    }
+
+    #[test]
+    fn when_invalid() {
+        for s in ["", "0", "0+", "1+0", "5..3", "a", "1..", "..3", "1+x", "-1"] {
+            assert!(When::parse(s).is_none(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn delay() {
+        assert_eq!(parse_delay("2s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_delay("300ms"), Some(Duration::from_millis(300)));
+        assert_eq!(parse_delay("10us"), Some(Duration::from_micros(10)));
+        assert_eq!(parse_delay("10"), Some(Duration::from_micros(10)));
+        assert_eq!(parse_delay("5ns"), Some(Duration::from_nanos(5)));
+        assert_eq!(parse_delay(""), None);
+        assert_eq!(parse_delay("ms"), None);
+        assert_eq!(parse_delay("1m"), None);
+        assert_eq!(parse_delay("-1s"), None);
+        assert_eq!(parse_delay("1.5s"), None);
+    }
+}
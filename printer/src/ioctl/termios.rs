@@ -126,7 +126,7 @@ fn write_ioctl_arg(printer: &Printer, req: u64, value: u64, pid: types::Pid, e:
             return Ok(true)
         },
         v if (v >= TCGETS && v <= TCSBRKP) || ( v >= TIOCSBRK && v<= TIOCGSID) => {
-            printer.write(b"...")?;
+            printer.write_text(b"...")?;
             return Ok(true)
         },
         TCGETS2 => if e.is_exit() {
@@ -150,7 +150,7 @@ fn write_ioctl_arg(printer: &Printer, req: u64, value: u64, pid: types::Pid, e:
             return Ok(true)
         },
         TIOCGPTPEER => {
-            printer.write(b"...")?;
+            printer.write_text(b"...")?;
             return Ok(true)
         },
         _ => return Ok(false),
@@ -161,7 +161,7 @@ fn write_ioctl_arg(printer: &Printer, req: u64, value: u64, pid: types::Pid, e:
 fn write_ioctl_arg_nopeek(printer: &Printer, req: u64, value: u64) -> std::result::Result<bool, std::io::Error> {
     match req as u32 {
         v if (v >= TCGETS && v <= TCSBRKP) || ( v >= TIOCSBRK && v<= TIOCGSID) => {
-            printer.write(b"...")?;
+            printer.write_text(b"...")?;
             return Ok(true)
         },
         TCGETS2 => { },
@@ -170,7 +170,7 @@ fn write_ioctl_arg_nopeek(printer: &Printer, req: u64, value: u64) -> std::resul
         TIOCSPTLCK | TIOCSIG => { },
         TIOCGDEV | TIOCGPKT | TIOCGPTLCK | TIOCGEXCL => { },
         TIOCGPTPEER => {
-            printer.write(b"...")?;
+            printer.write_text(b"...")?;
             return Ok(true)
         },
         _ => return Ok(false),
@@ -181,26 +181,26 @@ fn write_ioctl_arg_nopeek(printer: &Printer, req: u64, value: u64) -> std::resul
 
 impl crate::Print for termios2 {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".c_iflag = ")?; printer.write_number(self.c_iflag, &FORMATS::HEX)?;
-        printer.write(b", .c_oflag = ")?; printer.write_number(self.c_oflag, &FORMATS::HEX)?;
-        printer.write(b", .c_cflag = ")?; printer.write_number(self.c_cflag, &FORMATS::HEX)?;
-        printer.write(b", .c_lflag = ")?; printer.write_number(self.c_lflag, &FORMATS::HEX)?;
-        printer.write(b", .c_line = ")?; printer.write_number(self.c_line, &FORMATS::HEX)?;
-        printer.write(b", .c_cc = ")?; printer.write_as_hex(&self.c_cc)?;
-        printer.write(b", .c_ipeed = ")?; printer.write_number(self.c_ispeed, &FORMATS::DEC)?;
-        printer.write(b", .c_opeed = ")?; printer.write_number(self.c_ospeed, &FORMATS::DEC)?;
+        printer.write_member("c_iflag")?; printer.write_number(self.c_iflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_oflag")?; printer.write_number(self.c_oflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_cflag")?; printer.write_number(self.c_cflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_lflag")?; printer.write_number(self.c_lflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_line")?; printer.write_number(self.c_line, &FORMATS::HEX)?;
+        printer.write_next_member("c_cc")?; printer.write_as_hex(&self.c_cc)?;
+        printer.write_next_member("c_ipeed")?; printer.write_number(self.c_ispeed, &FORMATS::DEC)?;
+        printer.write_next_member("c_opeed")?; printer.write_number(self.c_ospeed, &FORMATS::DEC)?;
         Ok(())
     }
 }
 
 impl crate::Print for termios {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".c_iflag = ")?; printer.write_number(self.c_iflag, &FORMATS::HEX)?;
-        printer.write(b", .c_oflag = ")?; printer.write_number(self.c_oflag, &FORMATS::HEX)?;
-        printer.write(b", .c_cflag = ")?; printer.write_number(self.c_cflag, &FORMATS::HEX)?;
-        printer.write(b", .c_lflag = ")?; printer.write_number(self.c_lflag, &FORMATS::HEX)?;
-        printer.write(b", .c_line = ")?; printer.write_number(self.c_line, &FORMATS::HEX)?;
-        printer.write(b", .c_cc = ")?; printer.write_as_hex(&self.c_cc)?;
+        printer.write_member("c_iflag")?; printer.write_number(self.c_iflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_oflag")?; printer.write_number(self.c_oflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_cflag")?; printer.write_number(self.c_cflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_lflag")?; printer.write_number(self.c_lflag, &FORMATS::HEX)?;
+        printer.write_next_member("c_line")?; printer.write_number(self.c_line, &FORMATS::HEX)?;
+        printer.write_next_member("c_cc")?; printer.write_as_hex(&self.c_cc)?;
         Ok(())
     }
 }
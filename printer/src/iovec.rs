@@ -17,7 +17,7 @@ macro_rules! iovec_impl_print {
     ($type:ty) => {
         impl crate::Print for $type {
             fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                printer.write(b".io_base = ")?;
+                printer.write_member("io_base")?;
                 if let crate::config::PrivData::IOVEC(s) = printer.prv_data.get() {
                     let min = std::cmp::min(s, self.iov_len as usize);
                     printer.peek_write_maybe_ascii_str(self.iov_base as types::Ptr, min, pid, e)?;
@@ -25,7 +25,7 @@ macro_rules! iovec_impl_print {
                 } else {
                     printer.peek_write_maybe_ascii_str(self.iov_base as types::Ptr, self.iov_len as usize, pid, e)?;
                 }
-                printer.write(b", .io_len = ")?;
+                printer.write_next_member("io_len")?;
                 printer.write_number(self.iov_len, &FORMATS::DEC)?;
                 Ok(())
             }
@@ -45,3 +45,20 @@ macro_rules! iovec_impl_print {
 iovec_impl_print!(iovec);
 iovec_impl_print!(compat_iovec);
 
+macro_rules! iovec_impl_peek {
+    ($type:ty) => {
+        impl $type {
+            /// Peek iovec array as pairs of base address and length
+            pub(crate) fn peek_array(pid: types::Pid, addr: types::Ptr, cnt: usize) -> std::result::Result<Vec<(types::Ptr, usize)>, std::io::Error> {
+                (0..cnt).map(|i| {
+                    let v = peek::peek_data::<$type>(pid, addr + i * std::mem::size_of::<$type>())?;
+                    Ok((v.iov_base as types::Ptr, v.iov_len as usize))
+                }).collect()
+            }
+        }
+    };
+}
+
+iovec_impl_peek!(iovec);
+iovec_impl_peek!(compat_iovec);
+
@@ -0,0 +1,87 @@
+const HEX: [u8; 16] = *b"0123456789abcdef";
+
+/// Write bytes as JSON string, valid UTF-8 is passed through, control characters and invalid bytes are escaped as \u00XX
+pub fn write_str(printer: &crate::Printer, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+    write_escaped(&mut |b| printer.write(b), buf)
+}
+
+/// Write bytes as JSON string by write function
+fn write_escaped<F>(write: &mut F, buf: &[u8]) -> std::result::Result<(), std::io::Error>
+where F: FnMut(&[u8]) -> std::result::Result<(), std::io::Error> {
+    write(b"\"")?;
+    for chunk in buf.utf8_chunks() {
+        write_valid(write, chunk.valid().as_bytes())?;
+        for c in chunk.invalid() {
+            write(&[b'\\', b'u', b'0', b'0', HEX[(c >> 4) as usize], HEX[(c & 0xf) as usize]])?;
+        }
+    }
+    write(b"\"")
+}
+
+/// Write valid UTF-8 escaping quote, backslash and control characters
+fn write_valid<F>(write: &mut F, buf: &[u8]) -> std::result::Result<(), std::io::Error>
+where F: FnMut(&[u8]) -> std::result::Result<(), std::io::Error> {
+    let mut head = 0;
+    for (i, c) in buf.iter().enumerate() {
+        let esc: &[u8] = match *c {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            c if c < 0x20 || c == 0x7f => &[b'\\', b'u', b'0', b'0', HEX[(c >> 4) as usize], HEX[(c & 0xf) as usize]],
+            _ => continue,
+        };
+        write(&buf[head..i])?;
+        write(esc)?;
+        head = i + 1;
+    }
+    write(&buf[head..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escaped(buf: &[u8]) -> String {
+        let mut out = vec![];
+        write_escaped(&mut |b| { out.extend_from_slice(b); Ok(()) }, buf).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(escaped(b""), r#""""#);
+        assert_eq!(escaped(b"/etc/passwd"), r#""/etc/passwd""#);
+    }
+
+    #[test]
+    fn quote_backslash() {
+        assert_eq!(escaped(br#"a"b"#), r#""a\"b""#);
+        assert_eq!(escaped(br"a\b"), r#""a\\b""#);
+        assert_eq!(escaped(br#"\""#), r#""\\\"""#);
+    }
+
+    #[test]
+    fn control() {
+        assert_eq!(escaped(b"a\nb\rc\td"), r#""a\nb\rc\td""#);
+        assert_eq!(escaped(b"\x00\x01\x1f\x7f"), r#""\u0000\u0001\u001f\u007f""#);
+        assert_eq!(escaped(b" ~"), r#"" ~""#);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(escaped(b"a\xffb"), r#""a\u00ffb""#);
+        assert_eq!(escaped(b"\x80\x80"), r#""\u0080\u0080""#);
+        // truncated sequence of 3 bytes
+        assert_eq!(escaped(b"\xe3\x81"), r#""\u00e3\u0081""#);
+        assert_eq!(escaped(b"\xe3\x81\n"), r#""\u00e3\u0081\n""#);
+    }
+
+    #[test]
+    fn multibyte() {
+        assert_eq!(escaped("é日本🦀".as_bytes()), "\"é日本🦀\"");
+        assert_eq!(escaped("\"日\\本\"".as_bytes()), r#""\"日\\本\"""#);
+        assert_eq!(escaped(b"\xe6\x97\xa5\xff"), "\"日\\u00ff\"");
+    }
+}
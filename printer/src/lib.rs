@@ -22,9 +22,21 @@ mod time;
 mod sys;
 mod ioctl;
 mod poll;
+mod summary;
+mod json;
+mod signal;
+mod clone;
+mod futex;
+mod fd;
+mod path;
+mod regex;
+mod inject;
+mod redirect;
+mod dump;
 
 use number::ToString;
 use config::{TYPES, FORMATS};
+pub use path::glob;
 
 #[allow(unused_macros)]
 macro_rules! LINE { () => { println!("{}", line!()) } }
@@ -49,7 +61,7 @@ macro_rules! print_bit_number {
 macro_rules! print_bit_pointer {
     ($self:ident, $value:expr, $summery:expr) => {
         if $value == 0 {
-            $self.write(b"NULL")
+            $self.write_null()
         } else  {
             if $summery.is_64() {
                 $self.write_number_as_pointer($value as a64::Ptr)
@@ -119,11 +131,86 @@ trait Print {
     fn total_size(&self) -> usize where Self: Sized { std::mem::size_of::<Self>() }
 }
 
+/// Timestamp kind printed at head of log line
+#[derive(Copy, Clone, PartialEq)]
+pub enum TIMESTAMP {
+    /// No timestamp
+    NONE,
+    /// Local wall clock time with microseconds
+    WALL,
+    /// Elapsed time from trace start
+    RELATIVE,
+}
+
+/// Log output format
+#[derive(Copy, Clone, PartialEq)]
+pub enum OUTPUT {
+    /// Human readable text, entry and exit as separated lines
+    TEXT,
+    /// JSON Lines, one object per completed syscall
+    JSON,
+}
+
+/// Syscall result printed
+#[derive(Copy, Clone, PartialEq)]
+pub enum RESULT {
+    /// Print regardless of result
+    ANY,
+    /// Print only succeeded syscalls
+    SUCCESS,
+    /// Print only failed syscalls
+    FAILED,
+}
+
+/// Options of log line format
+struct OutputOpts {
+    timestamp: TIMESTAMP,
+    duration: bool,
+    output: OUTPUT,
+    merge: bool,
+    /// Output only summary
+    quiet: bool,
+    /// Annotate fd with its path
+    fd_path: bool,
+}
+
+impl OutputOpts {
+    fn new() -> Self {
+        OutputOpts{ timestamp: TIMESTAMP::NONE, duration: false, output: OUTPUT::TEXT, merge: false, quiet: false, fd_path: false }
+    }
+}
+
+/// Filters of printed syscalls, in addition to selection by syscall name
+struct Filters {
+    paths: path::PathFilter,
+    result: RESULT,
+    errnos: Vec<i32>,
+}
+
+impl Filters {
+    fn new() -> Self {
+        Filters{ paths: path::PathFilter::new(), result: RESULT::ANY, errnos: vec![] }
+    }
+}
+
 /// Module interface struct
 pub struct Printer {
     writer: logger::Logger,
     conf: config::Config,
     prv_data: std::cell::Cell<config::PrivData>,
+    clock: time::Clock,
+    /// Time of event being output, current time if None
+    event_time: std::cell::Cell<Option<std::time::Instant>>,
+    opts: OutputOpts,
+    summary: Option<summary::Summary>,
+    pending: std::cell::RefCell<std::collections::BTreeMap<types::Pid, Vec<u8>>>,
+    tgid: std::cell::RefCell<std::collections::BTreeMap<types::Pid, types::Pid>>,
+    fds: std::cell::RefCell<fd::FdTable>,
+    filters: Filters,
+    path_skipped: std::cell::RefCell<std::collections::BTreeSet<types::Pid>>,
+    inject: std::cell::RefCell<inject::Inject>,
+    redirect: redirect::PathRedirect,
+    dump: std::cell::RefCell<dump::DumpIo>,
 }
 
 impl Printer {
@@ -136,6 +223,113 @@ impl Printer {
         self.writer.flush_line()
     }
 
+    fn is_json(&self) -> bool {
+        self.opts.output == OUTPUT::JSON
+    }
+
+    fn write_member(&self, name: &str) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            self.write(b"\"")?;
+            self.write(name.as_bytes())?;
+            self.write(b"\": ")
+        } else {
+            self.write(b".")?;
+            self.write(name.as_bytes())?;
+            self.write(b" = ")
+        }
+    }
+
+    fn write_next_member(&self, name: &str) -> std::result::Result<(), std::io::Error> {
+        self.write(b", ")?;
+        self.write_member(name)
+    }
+
+    fn write_key(&self, name: &str) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            self.write(b"\"")?;
+            self.write(name.as_bytes())?;
+            self.write(b"\": ")
+        } else {
+            self.write(name.as_bytes())?;
+            self.write(b": ")
+        }
+    }
+
+    /// Write symbolic name or bare text, quoted as string for JSON
+    fn write_text(&self, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            json::write_str(self, buf)
+        } else {
+            self.write(buf)
+        }
+    }
+
+    fn write_null(&self) -> std::result::Result<(), std::io::Error> {
+        self.write(if self.is_json() { b"null" } else { b"NULL" })
+    }
+
+    fn write_array_begin(&self) -> std::result::Result<(), std::io::Error> {
+        self.write(if self.is_json() { b"[" } else { b"{" })
+    }
+
+    fn write_array_end(&self) -> std::result::Result<(), std::io::Error> {
+        self.write(if self.is_json() { b"]" } else { b"}" })
+    }
+
+    fn write_flags_begin(&self) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() { self.write(b"[") } else { Ok(()) }
+    }
+
+    fn write_flags_sep(&self) -> std::result::Result<(), std::io::Error> {
+        self.write(if self.is_json() { b", " } else { b" | " })
+    }
+
+    fn write_flags_end(&self) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() { self.write(b"]") } else { Ok(()) }
+    }
+
+    /// Write string with quote, non graphic characters are escaped
+    fn write_str(&self, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            json::write_str(self, buf)
+        } else {
+            self.write(b"\"")?;
+            self.write_graph_ascii_or_hex(buf)?;
+            self.write(b"\"")
+        }
+    }
+
+    /// Write string with quote, dump as hex if maybe binary
+    fn write_maybe_ascii_str(&self, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            json::write_str(self, buf)
+        } else {
+            self.write(b"\"")?;
+            self.write_maybe_ascii(buf)?;
+            self.write(b"\"")
+        }
+    }
+
+    /// Write bytes as hex block, {0011..} or "0011.." for JSON
+    fn write_hex_block(&self, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            return self.write_as_hex(buf);
+        }
+        self.write(b"{")?;
+        self.write_as_hex(buf)?;
+        self.write(b"}")
+    }
+
+    fn write_quoted_if_json(&self, buf: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() {
+            self.write(b"\"")?;
+            self.write(buf)?;
+            self.write(b"\"")
+        } else {
+            self.write(buf)
+        }
+    }
+
     fn peek_vec(&self, addr: types::Ptr, size: usize, pid: types::Pid) -> std::result::Result<Vec<u8>, std::io::Error> {
         let size = std::cmp::min(4096 as usize, size);
         let mut buf = Vec::<u8>::with_capacity(size);
@@ -210,7 +404,9 @@ impl Printer {
     }
 
     fn write_as_hex(&self, hex: &[u8]) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() { self.write(b"\"")?; }
         for h in hex.iter() { self.write_hex(*h)?; }
+        if self.is_json() { self.write(b"\"")?; }
         Ok(())
     }
 
@@ -260,42 +456,32 @@ impl Printer {
         self.write_graph_ascii_or_hex(buf.as_slice())
     }
 
-    fn peek_write_maybe_ascii(&self, addr: types::Ptr, size: usize, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        let buf = self.peek_vec(addr, size, pid)?;
-        self.write_maybe_ascii(buf.as_slice())
-    }
-
-    fn peek_write_maybe_ascii_str(&self, addr: types::Ptr, size: usize, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    fn peek_write_maybe_ascii_str(&self, addr: types::Ptr, size: usize, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")?;
+            self.write_null()
         } else {
-            self.write(b"\"")?;
-            self.peek_write_maybe_ascii(addr, size, pid, e)?;
-            self.write(b"\"")?;
+            let buf = self.peek_vec(addr, size, pid)?;
+            self.write_maybe_ascii_str(buf.as_slice())
         }
-        Ok(())
     }
 
     fn peek_write_as_hex(&self, addr: types::Ptr, size: usize, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")?;
+            self.write_null()
         } else {
-            self.write(b"{")?;
             let buf = self.peek_vec(addr, size, pid)?;
-            self.write_as_hex(buf.as_slice())?;
-            self.write(b"}")?;
+            self.write_hex_block(buf.as_slice())
         }
-        Ok(())
     }
 
     fn peek_write_number<T: number::ToString>(&self, addr: types::Ptr, fmt: &FORMATS, pid: types::Pid, summery: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")
+            self.write_null()
         } else  {
             if let Ok(value) = peek::peek_data::<T>(pid, addr) {
-                self.write(b"{")?;
+                self.write_array_begin()?;
                 self.write_number(value, fmt)?;
-                self.write(b"}")
+                self.write_array_end()
             } else {
                 print_bit_pointer!(self, addr, summery)
             }
@@ -304,41 +490,41 @@ impl Printer {
 
     fn peek_write(&self, addr: types::Ptr, size: usize, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         let buf = self.peek_vec(addr, size, pid)?;
-        self.write(buf.as_slice())
+        self.write_text(buf.as_slice())
     }
 
     fn write_number_as_pointer<T: number::ToPtrString>(&self, value: T) -> std::result::Result<(), std::io::Error> {
-        self.write(value.ptoa().as_bytes())
+        self.write_quoted_if_json(value.ptoa().as_bytes())
     }
 
     fn write_number_zero_fill<T: number::ToPtrString>(&self, value: T) -> std::result::Result<(), std::io::Error> {
-        self.write(value.ptoa().as_bytes())
+        self.write_quoted_if_json(value.ptoa().as_bytes())
     }
 
     fn write_number<T: number::ToString>(&self, value: T, fmt: &FORMATS) -> std::result::Result<(), std::io::Error> {
         match fmt {
-            FORMATS::HEX => self.write(value.htoa().as_bytes()),
+            FORMATS::HEX => self.write_quoted_if_json(value.htoa().as_bytes()),
             FORMATS::DEC => self.write(value.dtoa().as_bytes()),
-            FORMATS::OCT => self.write(value.otoa().as_bytes()),
+            FORMATS::OCT => self.write_quoted_if_json(value.otoa().as_bytes()),
         }
     }
 
     fn write_number_array<T: number::ToString + Copy>(&self, value: &[T], fmt: &FORMATS) -> std::result::Result<(), std::io::Error> {
-        self.write(b"{")?;
+        self.write_array_begin()?;
         for (i,v) in value.iter().enumerate() {
             if i != 0 {
                 self.write(b", ")?;
             }
             self.write_number(*v, fmt)?;
         }
-        self.write(b"}")?;
+        self.write_array_end()?;
         Ok(())
     }
 
     fn try_write_enum<T: PartialEq>(&self, value: T, tbl: &[(T, &'static str)]) -> std::result::Result<bool, std::io::Error> {
         for (v,n) in tbl.iter() {
             if value == *v {
-                self.write(n.as_bytes())?;
+                self.write_text(n.as_bytes())?;
                 return Ok(true);
             }
         }
@@ -347,54 +533,62 @@ impl Printer {
     fn write_enum<T: PartialEq + number::ToString>(&self, value: T, tbl: &[(T, &'static str)]) -> std::result::Result<(), std::io::Error> {
         for (v,n) in tbl.iter() {
             if value == *v {
-                return self.write(n.as_bytes());
+                return self.write_text(n.as_bytes());
             }
         }
         self.write_number(value, &FORMATS::HEX)
     }
 
-    fn write_mask_enum<T>(&self, value: T, tbl: &[(T, &'static str)]) -> std::result::Result<(), std::io::Error>
+    /// Write flag names contained in value without begin and end of flags
+    /// # Arguments
+    /// * `tail` - Some flag is already written before
+    fn write_mask_enum_items<T>(&self, value: T, tbl: &[(T, &'static str)], tail: bool) -> std::result::Result<(), std::io::Error>
     where
         T: number::ToString + From<u8> + Copy + PartialEq + std::ops::BitAnd<Output = T> + std::ops::BitXor<Output = T>,
     {
-        if value == T::from(0 as u8) {
-            self.write(b"0")?;
-            return Ok(())
-        }
         let mut value = value;
-        let mut tail = false;
+        let mut tail = tail;
         for (v,n) in tbl.iter() {
             if (value & *v) == *v  {
                 if tail {
-                    self.write(b" | ")?;
+                    self.write_flags_sep()?;
                 }
-                self.write(n.as_bytes())?;
+                self.write_text(n.as_bytes())?;
                 tail = true;
                 value = value ^ *v;
             }
         }
         if value != T::from(0 as u8) {
             if tail {
-                self.write(b" | ")?;
+                self.write_flags_sep()?;
             }
             self.write_number(value, &FORMATS::HEX)?;
         }
         Ok(())
     }
 
+    fn write_mask_enum<T>(&self, value: T, tbl: &[(T, &'static str)]) -> std::result::Result<(), std::io::Error>
+    where
+        T: number::ToString + From<u8> + Copy + PartialEq + std::ops::BitAnd<Output = T> + std::ops::BitXor<Output = T>,
+    {
+        if value == T::from(0 as u8) {
+            return self.write(if self.is_json() { b"[]" } else { b"0" });
+        }
+        self.write_flags_begin()?;
+        self.write_mask_enum_items(value, tbl, false)?;
+        self.write_flags_end()
+    }
+
     fn peek_write_str_null_sentinel(&self, addr: types::Ptr, pid: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")?;
+            self.write_null()
         } else {
-            self.write(b"\"")?;
-            self.write_graph_ascii_or_hex(&peek::peek_until_null(pid, addr)?)?;
-            self.write(b"\"")?;
+            self.write_str(&peek::peek_until_null(pid, addr)?)
         }
-        Ok(())
     }
 
     fn peek_write_execve_str_args(&self, addr: types::Ptr, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        self.write(b"{")?;
+        self.write_array_begin()?;
         let mut addr = addr;
         loop {
             if e.is_64() {
@@ -410,8 +604,8 @@ impl Printer {
             }
             self.write(b", ")?;
         }
-        self.write(b"NULL")?;
-        self.write(b"}")
+        self.write_null()?;
+        self.write_array_end()
     }
 
     fn write_struct_with_tail<T: Print>(&self, data: &T, tail: &[u8], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
@@ -448,7 +642,7 @@ impl Printer {
 
     fn peek_write_struct_impl<T: Print>(&self, addr: types::Ptr, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<usize, std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")?;
+            self.write_null()?;
             Ok(0)
         } else {
             let data = peek::peek_data::<T>(pid, addr)?;
@@ -484,7 +678,7 @@ impl Printer {
 
     fn peek_write_struct_array<T: Print>(&self, addr: types::Ptr, elem: usize, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")
+            self.write_null()
         } else {
             self.peek_write_flex_tail_struct_array::<T>(addr, elem * std::mem::size_of::<T>() , pid, e)
         }
@@ -493,36 +687,42 @@ impl Printer {
     fn write_flex_tail_struct_array_from_buf<T: Print>(&self, buf: &[u8], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         let mut len = 0;
         let bytes = buf.len();
-        self.write(b"{")?;
+        self.write_array_begin()?;
         while len < bytes {
-            if len != 0 {
-                T::print_array_delim(self, pid, e)?;
-            } else {
-                T::print_array_prefix(self, pid, e)?;
-            }
+            self.write_array_delim::<T>(len == 0, pid, e)?;
             len += self.write_struct_from_buf::<T>(&buf[len..], pid, e)?;
         }
-        T::print_array_suffix(self, pid, e)?;
-        self.write(b"}")
+        self.write_array_suffix::<T>(pid, e)?;
+        self.write_array_end()
+    }
+
+    /// Write delimiter or prefix of array, JSON is always single line
+    fn write_array_delim<T: Print>(&self, head: bool, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        match (head, self.is_json()) {
+            (true, true) => Ok(()),
+            (false, true) => self.write(b", "),
+            (true, false) => T::print_array_prefix(self, pid, e),
+            (false, false) => T::print_array_delim(self, pid, e),
+        }
+    }
+
+    fn write_array_suffix<T: Print>(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        if self.is_json() { Ok(()) } else { T::print_array_suffix(self, pid, e) }
     }
 
     fn write_struct_array<T: Print>(&self, array: &[T], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        self.write(b"{")?;
+        self.write_array_begin()?;
         for (index, elem) in array.iter().enumerate() {
-            if index != 0 {
-                T::print_array_delim(self, pid, e)?;
-            } else {
-                T::print_array_prefix(self, pid, e)?;
-            }
+            self.write_array_delim::<T>(index == 0, pid, e)?;
             elem.print(self, pid, e)?;
         }
-        T::print_array_suffix(self, pid, e)?;
-        self.write(b"}")
+        self.write_array_suffix::<T>(pid, e)?;
+        self.write_array_end()
     }
 
     fn peek_write_flex_tail_struct_array<T: Print>(&self, addr: types::Ptr, bytes: usize, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         if addr == 0 {
-            self.write(b"NULL")
+            self.write_null()
         } else {
             let buf = self.peek_vec_align::<T>(addr, bytes, pid)?;
             self.write_flex_tail_struct_array_from_buf::<T>(&buf, pid, e)
@@ -534,7 +734,7 @@ impl Printer {
         U: Fn(&Printer, &[u8], types::Pid, &peek::SyscallSummery) -> std::result::Result<(), std::io::Error>
     {
         if addr == 0 {
-            self.write(b"NULL")
+            self.write_null()
         } else {
             let buf = self.peek_vec_align::<T>(addr, size, pid)?;
             cb(self, buf.as_slice(), pid, e)
@@ -542,7 +742,26 @@ impl Printer {
     }
 
     fn write_any_type(&self, value: u64, print: &TYPES, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        let res = match print {
+        if self.is_json() {
+            // partial output on error breaks JSON, so write raw value instead of it
+            self.writer.begin_capture();
+            let res = self.write_any_type_impl(value, print, pid, e);
+            let buf = self.writer.end_capture();
+            return match res {
+                Ok(_) => self.write(&buf),
+                Err(_) => self.write_number(value, &FORMATS::HEX),
+            };
+        }
+        if self.write_any_type_impl(value, print, pid, e).is_err() {
+            self.write(b"::")?;
+            self.write_number(value, &FORMATS::HEX)?;
+            self.write(b"::")?;
+        };
+        Ok(())
+    }
+
+    fn write_any_type_impl(&self, value: u64, print: &TYPES, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        match print {
             TYPES::U8(fmt) => { self.write_number(value as u8, fmt) },
             TYPES::U16(fmt) => { self.write_number(value as u16, fmt) },
             TYPES::U32(fmt) => { self.write_number(value as u32, fmt) },
@@ -560,7 +779,7 @@ impl Printer {
 
             TYPES::U64LOW => {
                 self.prv_data.set(config::PrivData::U64LOW(value));
-                self.write(b"...")
+                self.write_text(b"...")
             },
             TYPES::U64HIGH(fmt) => {
                 let hi = value;
@@ -598,11 +817,21 @@ impl Printer {
                 }
             },
             TYPES::Clockid => { time::write_clockid(self, value, e) },
-            TYPES::DirFd => { open::write_dir_fd(self, value, e) },
+            TYPES::CloneFlag => { clone::write_flag(self, value, e) },
+            TYPES::CloneargsPtrLenArg2 => { self.peek_write_callback::<u64, _>(value as types::Ptr, e.argn(peek::Arg::TWO) as usize, clone::write_clone_args, pid, e) },
+            TYPES::CloneChildTidPtr => { clone::write_child_tid(self, value, pid, e) },
+            TYPES::CloneParentTidPtr => { clone::write_parent_tid(self, value, pid, e) },
+            TYPES::DirFd => { open::write_dir_fd(self, value, pid, e) },
             TYPES::EpollctlOp => { epoll::write_op(self, value, e) },
             TYPES::EpolleventPtr => { peek_write_struct!(self, value, epoll::epoll_event, pid, e) },
             TYPES::EpolleventArrayPtrLenArgR => { peek_write_struct_array!(self, value, epoll::epoll_event, e.return_value()?, pid, e) },
+            TYPES::Fd => { fd::write_fd(self, value, pid, e) },
             TYPES::FdFlag => { open::write_fd_flags(self, value, e) },
+            TYPES::FutexOp => { futex::write_op(self, value, e) },
+            TYPES::FutexTimeout => { futex::write_timeout(self, value, pid, e) },
+            TYPES::FutexUaddrPtr => { futex::write_uaddr(self, value, pid, e) },
+            TYPES::FutexVal3 => { futex::write_val3(self, value, e) },
+            TYPES::FutexwaitvPtrLenArg2 => { peek_write_struct_array!(self, value, futex::futex_waitv, e.argn(peek::Arg::TWO), pid, e) },
             TYPES::IoctlReqest => { ioctl::write_ioctl_request(self, value) },
             TYPES::IoctlArgNoPeek => { ioctl::write_ioctl_arg_nopeek(self, value, e) },
             TYPES::IoctlArg => { ioctl::write_ioctl_arg(self, value, pid, e) },
@@ -634,6 +863,12 @@ impl Printer {
             TYPES::Rlimit64Ptr => { peek_write_struct!(self, value, rlimit::rlimit64, pid, e) },
             TYPES::RlimitPtr => { peek_write_bit_struct!(self, value, rlimit::rlimit, rlimit::compat_rlimit, pid, e) },
             TYPES::SendFlag => { socket::write_send_flag(self, value, e) },
+            TYPES::SigactionPtr => { peek_write_bit_struct!(self, value, signal::sigaction, signal::compat_sigaction, pid, e) },
+            TYPES::SiginfoPtr => { peek_write_bit_struct!(self, value, signal::siginfo, signal::compat_siginfo, pid, e) },
+            TYPES::SigprocmaskHow => { signal::write_how(self, value, e) },
+            TYPES::Signal => { signal::write_signal(self, value, e) },
+            TYPES::SigsetPtrLenArg2 => { signal::peek_write_sigset(self, value, e.argn(peek::Arg::TWO) as usize, pid, e) },
+            TYPES::SigsetPtrLenArg4 => { signal::peek_write_sigset(self, value, e.argn(peek::Arg::FUR) as usize, pid, e) },
             TYPES::SockaddrPtrLenArg3 => { self.peek_write_callback::<u64, _>(value as types::Ptr, e.argn(peek::Arg::THR) as usize, sockaddr::write_sockaddr, pid, e) },
             TYPES::SockaddrPtrLenArg3Ptr => {
                 let addr = e.argn(peek::Arg::THR) as types::Ptr;
@@ -658,14 +893,7 @@ impl Printer {
 
             TYPES::UNDEF => { self.write_number(value, &FORMATS::HEX) },
             TYPES::SKIP | TYPES::NONE => { Ok(()) },
-        };
-
-        if res.is_err() {
-            self.write(b"::")?;
-            self.write_number(value, &FORMATS::HEX)?;
-            self.write(b"::")?;
-        };
-        Ok(())
+        }
     }
 
     fn write_header_suf(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
@@ -676,12 +904,37 @@ impl Printer {
         self.write_width(e.sysname().as_bytes(), 20)
     }
 
+    fn now(&self) -> std::time::Instant {
+        self.event_time.get().unwrap_or_else(std::time::Instant::now)
+    }
+
+    fn write_timestamp(&self, t: std::time::Instant) -> std::result::Result<(), std::io::Error> {
+        match self.opts.timestamp {
+            TIMESTAMP::NONE => return Ok(()),
+            TIMESTAMP::WALL => time::write_wall_clock(self, &self.clock, t)?,
+            TIMESTAMP::RELATIVE => time::write_relative(self, &self.clock, t)?,
+        }
+        self.write(b" ")
+    }
+
+    fn write_duration(&self, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        match e.duration() {
+            Some(d) if self.opts.duration => {
+                self.write(b" ")?;
+                time::write_duration(self, d)
+            },
+            _ => Ok(()),
+        }
+    }
+
     fn write_entry_header(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_timestamp(e.entry_time())?;
         self.write(b"in  [")?;
         self.write_header_suf(pid, e)
     }
 
     fn write_exit_header(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_timestamp(e.exit_time().unwrap_or(e.entry_time()))?;
         self.write(b"out [")?;
         self.write_header_suf(pid, e)?;
         self.write(b"(...) = ")
@@ -692,12 +945,13 @@ impl Printer {
         let simple = conf.is_simple();
         let simple_type = TYPES::U64(FORMATS::HEX);
         let print = conf.get_print_info(e.is_64());
+        let dumped = self.dump.borrow().is_dumped(pid, e);
         for i in 0..a.len() {
             if print.args[i] == TYPES::NONE { break }
             if i != 0 {
                 self.write(b", ")?;
             }
-            let nopeek = conf.is_nopeek();
+            let nopeek = conf.is_nopeek() || (dumped && i == 1);
             let arg = if simple { &simple_type } else if nopeek { print.args[i].nopeek_type() } else { &print.args[i] };
             self.write_any_type(a[i], &arg, pid, e)?;
         }
@@ -710,22 +964,33 @@ impl Printer {
             if i != 0 {
                 self.write(b", ")?;
             }
-            self.write_width(a[i].htoa().as_bytes(), 20)?;
+            if self.is_json() {
+                self.write_number(a[i], &FORMATS::HEX)?;
+            } else {
+                self.write_width(a[i].htoa().as_bytes(), 20)?;
+            }
         }
         Ok(())
     }
 
-    fn write_ret_args_impl(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    /// Write arguments updated by syscall
+    /// # Arguments
+    /// * `head` - Nothing is written before in the same object, so no delimiter is required
+    fn write_ret_args_impl(&self, conf: &config::SyscallPrintConf, head: bool, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         let a = e.args();
         let simple = conf.is_simple();
         let simple_type = TYPES::U64(FORMATS::HEX);
         let print = conf.get_print_info(e.is_64());
+        let dumped = self.dump.borrow().done(pid).is_some();
+        let mut head = head;
         for i in 0..a.len() {
             if print.args[i] == TYPES::NONE { continue }
-            self.write(b", ")?;
-            self.write_number(i, &FORMATS::DEC)?;
-            self.write(b": ")?;
-            let arg = if simple { &simple_type } else { &print.args[i] };
+            if !head {
+                self.write(b", ")?;
+            }
+            head = false;
+            self.write_key(&i.to_string())?;
+            let arg = if simple { &simple_type } else if dumped && i == 1 { print.args[i].nopeek_type() } else { &print.args[i] };
             self.write_any_type(a[i], &arg, pid, e)?;
         }
         Ok(())
@@ -738,7 +1003,7 @@ impl Printer {
         } else if conf.is_nopeek() {
             Ok(())
         } else {
-            self.write_ret_args_impl(&conf, pid, e)
+            self.write_ret_args_impl(&conf, false, pid, e)
         }
     }
 
@@ -746,36 +1011,336 @@ impl Printer {
         match err.raw_os_error() {
             Some(r) if r >= 0 => errno::write_errno(self, r as u64, e),
             Some(r) if r < 0 => errno::write_errno(self, r.wrapping_abs() as u64, e),
-            _ => self.write(b"?"),
+            _ => self.write_text(b"?"),
         }
     }
 
     fn write_ret_impl(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_exit_header(pid, e)?;
+        self.write_ret_value(conf, pid, e)
+    }
+
+    /// Write return value, updated arguments and duration then end the line
+    fn write_ret_value(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
         let print = conf.get_print_info(e.is_64());
         let print_type = if conf.is_simple() {
             TYPES::U64(FORMATS::HEX)
         } else if conf.is_nopeek() {
             *print.ret.nopeek_type()
         } else { print.ret };
-        self.write_exit_header(pid, e)?;
         match e.return_value() {
             Ok(r) => {
                 self.write_any_type(r, &print_type, pid, e)?;
                 self.write_ret_args(pid, e)?;
+                self.write_dumped(pid)?;
             },
             Err(r) => self.write_errno(r, e)?,
         }
+        if self.is_injected(pid) {
+            self.write(b" (INJECTED)")?;
+        }
+        self.write_duration(e)?;
+        self.flush_line()
+    }
+
+    fn write_merged_entry_line(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_timestamp(e.entry_time())?;
+        self.write(b"[")?;
+        self.write_header_suf(pid, e)?;
+        if conf.is_undef() {
+            self.dump_args(e)
+        } else {
+            self.write(b"(")?;
+            self.write_args_impl(conf, pid, e)?;
+            self.write(b")")
+        }
+    }
+
+    fn write_resumed_header(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_timestamp(e.exit_time().unwrap_or(e.entry_time()))?;
+        self.write(b"[")?;
+        self.write_width(pid.dtoa().as_bytes(), 6)?;
+        self.write(b":")?;
+        self.write_width(e.sysnum().dtoa().as_bytes(), 6)?;
+        self.write(b"]<... ")?;
+        self.write(e.sysname().as_bytes())?;
+        self.write(b" resumed>")
+    }
+
+    /// Output syscall-entry lines kept for other processes as unfinished
+    fn flush_unfinished(&self, pid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        self.flush_unfinished_if(|p| p != pid)
+    }
+
+    /// Output kept syscall-entry lines of processes matched to filter as unfinished
+    fn flush_unfinished_if<F: Fn(types::Pid) -> bool>(&self, filter: F) -> std::result::Result<(), std::io::Error> {
+        // syscall-entry lines are kept until result is known if filtered by result
+        if self.is_ret_filtered() {
+            return Ok(());
+        }
+        let others: Vec<types::Pid> = self.pending.borrow().keys().filter(|p| filter(**p)).copied().collect();
+        for p in others {
+            if let Some(line) = self.pending.borrow_mut().remove(&p) {
+                let current = self.writer.select(Some(self.get_tgid(p)));
+                self.write(&line)?;
+                self.write(b" <unfinished ...>")?;
+                self.flush_line()?;
+                self.writer.select(current);
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep syscall-entry line until syscall-exit to output as one line
+    fn write_merged_entry(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.flush_unfinished(pid)?;
+        self.writer.begin_capture();
+        let r = self.write_merged_entry_line(conf, pid, e);
+        let line = self.writer.end_capture();
+        self.pending.borrow_mut().insert(pid, line);
+        r
+    }
+
+    fn write_merged_exit(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.flush_unfinished(pid)?;
+        let line = self.pending.borrow_mut().remove(&pid);
+        match line {
+            Some(line) => self.write(&line)?,
+            None => self.write_resumed_header(pid, e)?,
+        }
+        self.write(b" = ")?;
+        self.write_ret_value(conf, pid, e)
+    }
+
+    /// Select output destination of the process, if output separately per process
+    fn select_output(&self, pid: types::Pid) {
+        self.writer.select(Some(self.get_tgid(pid)));
+    }
+
+    fn get_tgid(&self, pid: types::Pid) -> types::Pid {
+        *self.tgid.borrow_mut().entry(pid).or_insert_with(|| peek::get_tgid(pid).unwrap_or(pid))
+    }
+
+    /// Write JSON object members known at syscall-entry, object is not closed
+    fn write_json_head(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write(b"{")?;
+        self.write_key("pid")?;
+        self.write_number(self.get_tgid(pid), &FORMATS::DEC)?;
+        self.write(b", ")?;
+        self.write_key("tid")?;
+        self.write_number(pid, &FORMATS::DEC)?;
+        self.write(b", ")?;
+        self.write_key("arch")?;
+        self.write(if e.is_64() { b"64" } else { b"32" })?;
+        self.write(b", ")?;
+        self.write_key("syscall")?;
+        self.write_text(e.sysname().as_bytes())?;
+        self.write(b", ")?;
+        self.write_key("nr")?;
+        self.write_number(e.sysnum(), &FORMATS::DEC)?;
+        if self.opts.timestamp != TIMESTAMP::NONE {
+            self.write(b", ")?;
+            self.write_key("time")?;
+            match self.opts.timestamp {
+                TIMESTAMP::WALL => time::write_wall_clock(self, &self.clock, e.entry_time())?,
+                _ => time::write_relative(self, &self.clock, e.entry_time())?,
+            }
+        }
+        self.write(b", ")?;
+        self.write_key("args")?;
+        self.write_array_begin()?;
+        if conf.is_undef() {
+            self.dump_args(e)?;
+        } else {
+            self.write_args_impl(conf, pid, e)?;
+        }
+        self.write_array_end()
+    }
+
+    /// Keep syscall-entry part of JSON object until syscall-exit
+    fn write_json_entry(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.writer.begin_capture();
+        let r = self.write_json_head(conf, pid, e);
+        let buf = self.writer.end_capture();
+        self.pending.borrow_mut().insert(pid, buf);
+        r
+    }
+
+    fn write_json_exit(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        let print = conf.get_print_info(e.is_64());
+        let print_type = if conf.is_simple() {
+            TYPES::U64(FORMATS::HEX)
+        } else if conf.is_nopeek() {
+            *print.ret.nopeek_type()
+        } else { print.ret };
+        match self.pending.borrow_mut().remove(&pid) {
+            Some(head) => self.write(&head)?,
+            None => self.write_json_head(conf, pid, e)?,
+        }
+        self.write(b", ")?;
+        match e.return_value() {
+            Ok(r) => {
+                self.write_key("ret")?;
+                self.write_any_type(r, &print_type, pid, e)?;
+                let ret_conf = self.conf.get_print_info_for_ret_args(e.uni_sysnum());
+                if !ret_conf.is_skip() && !ret_conf.is_nopeek() {
+                    self.write(b", ")?;
+                    self.write_key("ret_args")?;
+                    self.write(b"{")?;
+                    self.write_ret_args_impl(&ret_conf, true, pid, e)?;
+                    self.write(b"}")?;
+                }
+                self.write_dumped(pid)?;
+            },
+            Err(r) => {
+                self.write_key("errno")?;
+                self.write_errno(r, e)?;
+            },
+        }
+        if self.is_injected(pid) {
+            self.write(b", ")?;
+            self.write_key("injected")?;
+            self.write(b"true")?;
+        }
+        if let (true, Some(d)) = (self.opts.duration, e.duration()) {
+            self.write(b", ")?;
+            self.write_key("duration")?;
+            time::write_duration(self, d)?;
+        }
+        self.write(b"}")?;
         self.flush_line()
     }
 
+    /// Write head of process event line, all kept syscall-entry lines are output before it
+    fn write_event_header(&self, pid: types::Pid, event: &str) -> std::result::Result<(), std::io::Error> {
+        if self.opts.merge && !self.is_json() {
+            self.flush_unfinished_if(|_| true)?;
+        }
+        if self.is_json() {
+            self.write(b"{")?;
+            self.write_key("pid")?;
+            self.write_number(self.get_tgid(pid), &FORMATS::DEC)?;
+            self.write(b", ")?;
+            self.write_key("tid")?;
+            self.write_number(pid, &FORMATS::DEC)?;
+            if self.opts.timestamp != TIMESTAMP::NONE {
+                self.write(b", ")?;
+                self.write_key("time")?;
+                match self.opts.timestamp {
+                    TIMESTAMP::WALL => time::write_wall_clock(self, &self.clock, self.now())?,
+                    _ => time::write_relative(self, &self.clock, self.now())?,
+                }
+            }
+            self.write(b", ")?;
+            self.write_key("event")?;
+            self.write_text(event.as_bytes())
+        } else {
+            self.write_timestamp(self.now())?;
+            self.write(b"[")?;
+            self.write_width(pid.dtoa().as_bytes(), 6)?;
+            self.write(b"] ")
+        }
+    }
+
+    /// Dummy syscall summery for values not related to syscall, e.g. siginfo of signal delivery
+    fn native_summery() -> peek::SyscallSummery {
+        peek::SyscallSummery::new_dummy_entry(cfg!(target_pointer_width = "64"), arch::sys_uni::NR::sys_rt_sigqueueinfo, 0, [0; 6], 0)
+    }
+
+    fn add_summary(&self, e: &peek::SyscallSummery) {
+        if let Some(s) = &self.summary {
+            s.add(e);
+        }
+    }
+
+    /// Check whether result of syscall in progress is injected
+    fn is_injected(&self, pid: types::Pid) -> bool {
+        self.inject.borrow().fault(pid) != inject::FAULT::NONE
+    }
+
+    /// Write reference to dump file of data transferred by syscall
+    fn write_dumped(&self, pid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        let dump = self.dump.borrow();
+        let Some(d) = dump.done(pid) else { return Ok(()) };
+        if self.is_json() {
+            self.write(b", ")?;
+            self.write_key("dump")?;
+            self.write(b"{")?;
+            match d {
+                Ok((path, offset)) => {
+                    self.write_key("path")?;
+                    json::write_str(self, path.as_bytes())?;
+                    self.write(b", ")?;
+                    self.write_key("offset")?;
+                    self.write_number(*offset, &FORMATS::DEC)?;
+                },
+                Err(err) => {
+                    self.write_key("error")?;
+                    json::write_str(self, err.as_bytes())?;
+                },
+            }
+            self.write(b"}")
+        } else {
+            self.write(b" (dump: ")?;
+            match d {
+                Ok((path, offset)) => {
+                    self.write(path.as_bytes())?;
+                    self.write(b"@")?;
+                    self.write(offset.dtoa().as_bytes())?;
+                },
+                Err(err) => self.write(err.as_bytes())?,
+            }
+            self.write(b")")
+        }
+    }
+
     fn write_syscall_exit_and_cont(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        if self.is_fd_tracked() {
+            self.fds.borrow_mut().update(pid, self.get_tgid(pid), e);
+            self.dump.borrow_mut().update(pid, self.get_tgid(pid), e);
+        }
+        if self.dump.borrow().is_target(e.uni_sysnum()) {
+            let tgid = self.get_tgid(pid);
+            self.dump.borrow_mut().dump(pid, tgid, e);
+        }
+        let r = self.write_syscall_exit_and_cont_impl(pid, e);
+        self.inject.borrow_mut().exit(pid);
+        self.dump.borrow_mut().exit(pid);
+        r
+    }
+
+    fn write_syscall_exit_and_cont_impl(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        let path_skipped = self.path_skipped.borrow_mut().remove(&pid);
         match self.conf.get_print_info(e.uni_sysnum()) {
-            p if p.is_skip() => {
+            p if p.is_skip() || path_skipped => {
                 let _r = peek::cont_process(pid);
                 Ok(())
             },
+            _ if !self.is_ret_matched(e) => {
+                self.pending.borrow_mut().remove(&pid);
+                let _r = peek::cont_process(pid);
+                Ok(())
+            },
+            _ if self.opts.quiet => {
+                self.add_summary(e);
+                let _r = peek::cont_process(pid);
+                Ok(())
+            },
+            p if self.is_json() => {
+                self.add_summary(e);
+                let r = self.write_json_exit(&p, pid, e);
+                let _ = peek::cont_process(pid);
+                r
+            },
+            p if self.opts.merge => {
+                self.add_summary(e);
+                let r = self.write_merged_exit(&p, pid, e);
+                let _ = peek::cont_process(pid);
+                r
+            },
             p => {
-                let r = self.write_ret_impl(&p, pid, e);
+                self.add_summary(e);
+                let r = self.write_kept_entry_line(pid).and_then(|_| self.write_ret_impl(&p, pid, e));
                 let _ = peek::cont_process(pid);
                 r
             }
@@ -791,24 +1356,102 @@ impl Printer {
         Ok(())
     }
 
+    /// Get print config of syscall, the syscall is skipped if it does not touch paths of path filter
+    fn get_print_info_with_path(&self, pid: types::Pid, e: &peek::SyscallSummery) -> config::SyscallPrintConf {
+        let conf = self.conf.get_print_info(e.uni_sysnum());
+        // syscall-exit of skipped syscall may not stop on seccomp, so reset the state at each syscall-entry
+        self.path_skipped.borrow_mut().remove(&pid);
+        if conf.is_skip() || self.filters.paths.is_empty() || self.filters.paths.is_match(self, &conf, pid, e) {
+            conf
+        } else {
+            self.path_skipped.borrow_mut().insert(pid);
+            config::SyscallPrintConf::new_skip()
+        }
+    }
+
+    fn write_entry_line(&self, conf: &config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.write_entry_header(pid, e)?;
+        if conf.is_undef() {
+            self.dump_args(e)
+        } else {
+            self.write(b"(")?;
+            self.write_args_impl(conf, pid, e)?;
+            self.write(b")")
+        }
+    }
+
+    /// Output syscall-entry line kept until syscall-exit for result filter
+    fn write_kept_entry_line(&self, pid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        match self.pending.borrow_mut().remove(&pid) {
+            Some(line) => {
+                self.write(&line)?;
+                self.flush_line()
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn is_ret_filtered(&self) -> bool {
+        self.filters.result != RESULT::ANY || !self.filters.errnos.is_empty()
+    }
+
+    /// Check whether syscall result is matched to result filter
+    fn is_ret_matched(&self, e: &peek::SyscallSummery) -> bool {
+        match e.return_value() {
+            Ok(_) => self.filters.result != RESULT::FAILED && self.filters.errnos.is_empty(),
+            Err(err) => self.filters.result != RESULT::SUCCESS && (self.filters.errnos.is_empty() || err.raw_os_error().is_some_and(|r| self.filters.errnos.contains(&r.wrapping_abs()))),
+        }
+    }
+
+    /// Check whether syscall-exit of skipped syscall shall be stopped, for fd tracking, dump, injected result or delay
+    fn is_exit_required(&self, pid: types::Pid, e: &peek::SyscallSummery) -> bool {
+        (self.is_fd_tracked() && fd::is_tracked(e.uni_sysnum())) || self.dump.borrow().is_target(e.uni_sysnum()) || self.is_injected(pid)
+            || self.inject.borrow().delay(pid, false).is_some()
+    }
+
     fn write_syscall_entry_and_cont(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        match self.conf.get_print_info(e.uni_sysnum()) {
+        match self.get_print_info_with_path(pid, e) {
             p if p.is_skip() => {
-                peek::cont_process(pid)
+                if self.is_exit_required(pid, e) {
+                    peek::cont_process_to_exit(pid)
+                } else {
+                    peek::cont_process(pid)
+                }
+            },
+            _ if self.opts.quiet => {
+                peek::cont_process_to_exit(pid)
+            },
+            p if self.is_json() => {
+                let r = self.write_json_entry(&p, pid, e);
+                let _ = peek::cont_process_to_exit(pid);
+                r
+            },
+            p if self.opts.merge => {
+                let r = self.write_merged_entry(&p, pid, e);
+                let _ = peek::cont_process_to_exit(pid);
+                r
+            },
+            p if self.is_ret_filtered() => {
+                self.writer.begin_capture();
+                let r = self.write_entry_line(&p, pid, e);
+                let line = self.writer.end_capture();
+                self.pending.borrow_mut().insert(pid, line);
+                let _ = peek::cont_process_to_exit(pid);
+                r
             },
             p if p.is_undef() => {
-                let _r = peek::cont_process(pid);
+                let _r = peek::cont_process_to_exit(pid);
                 self.write_entry_header(pid, e)?;
                 self.dump_args(e)?;
                 self.flush_line()
             },
             p  if p.is_nopeek() => {
-                let _r = peek::cont_process(pid);
+                let _r = peek::cont_process_to_exit(pid);
                 self.write_syscall_args(&p, pid, e)
             },
             p  => {
                 let r = self.write_syscall_args(&p, pid, e);
-                let _ = peek::cont_process(pid);
+                let _ = peek::cont_process_to_exit(pid);
                 r
             },
         }
@@ -819,7 +1462,24 @@ impl Printer {
         let writer = logger::Logger::default();
         let conf = config::Config::new();
         let prv_data = std::cell::Cell::new(config::PrivData::NONE);
-        Printer{writer, conf, prv_data}
+        let clock = time::Clock::new();
+        Printer{
+            writer,
+            conf,
+            prv_data,
+            clock,
+            event_time: std::cell::Cell::new(None),
+            opts: OutputOpts::new(),
+            summary: None,
+            pending: std::cell::RefCell::new(std::collections::BTreeMap::new()),
+            tgid: std::cell::RefCell::new(std::collections::BTreeMap::new()),
+            fds: std::cell::RefCell::new(fd::FdTable::new()),
+            filters: Filters::new(),
+            path_skipped: std::cell::RefCell::new(std::collections::BTreeSet::new()),
+            inject: std::cell::RefCell::new(inject::Inject::new()),
+            redirect: redirect::PathRedirect::new(),
+            dump: std::cell::RefCell::new(dump::DumpIo::new()),
+        }
     }
 
     /// Output SyscallSummery to log destination
@@ -827,6 +1487,7 @@ impl Printer {
     /// * `pid` - A process ID of log output target
     /// * `e` - Syscall summery of log output target
     pub fn output_and_cont(&self, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
         if e.is_entry() {
             self.write_syscall_entry_and_cont(pid, e)
         } else  {
@@ -834,6 +1495,82 @@ impl Printer {
         }
     }
 
+    /// Check whether syscall is traced, the syscall is not traced if skip output and not required for fd path annotation
+    /// # Arguments
+    /// * `nr` - Target syscall
+    pub fn is_traced(&self, nr: arch::sys_uni::NR) -> bool {
+        !self.conf.get_print_info(nr).is_skip() || (self.is_fd_tracked() && fd::is_tracked(nr)) || self.inject.borrow().contains(nr)
+            || self.redirect.is_target(nr) || self.dump.borrow().is_target(nr)
+    }
+
+    /// Add fd to dump complete data of read and write syscalls
+    /// # Arguments
+    /// * `spec` - fd number, or glob pattern matched to path of fd
+    pub fn add_dump_io(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        self.dump.get_mut().add(spec)
+    }
+
+    /// Add path redirection rule
+    /// # Arguments
+    /// * `spec` - `FROM=TO`, path argument FROM is replaced with TO
+    pub fn add_redirect_path(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        self.redirect.add(spec)
+    }
+
+    /// Find path arguments to redirect at syscall-entry, return argument positions and replacement paths
+    /// # Arguments
+    /// * `pid` - A thread ID stopped at syscall-entry or syscall-exit
+    /// * `e` - Syscall summery
+    pub fn redirect_path(&self, pid: types::Pid, e: &peek::SyscallSummery) -> Vec<(usize, Vec<u8>)> {
+        if !e.is_entry() {
+            return vec![];
+        }
+        self.redirect.redirect(pid, e)
+    }
+
+    /// Add fault injection rule
+    /// # Arguments
+    /// * `spec` - `SET:OPTION[:OPTION...]`, SET is syscall names separated comma,
+    ///            OPTION is `error=ERRNO`, `retval=N`, `delay_enter=T`, `delay_exit=T` or `when=EXPR`, EXPR is `first[..last][+[step]]`
+    pub fn add_inject(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        self.inject.borrow_mut().add(spec)
+    }
+
+    /// Check whether fault is injected to the syscall at syscall-entry,
+    /// the syscall shall be skipped and its result shall be overwritten by `injected_return` at syscall-exit
+    /// # Arguments
+    /// * `pid` - A thread ID stopped at syscall-entry or syscall-exit
+    /// * `e` - Syscall summery
+    pub fn inject_entry(&self, pid: types::Pid, e: &peek::SyscallSummery) -> bool {
+        let mut inject = self.inject.borrow_mut();
+        if inject.is_empty() || !e.is_entry() {
+            return false;
+        }
+        inject.entry(pid, e.uni_sysnum()) && inject.fault(pid) != inject::FAULT::NONE
+    }
+
+    /// Return value injected to syscall in progress, -errno for error, shall be overwritten at syscall-exit
+    pub fn injected_return(&self, pid: types::Pid) -> Option<i64> {
+        match self.inject.borrow().fault(pid) {
+            inject::FAULT::NONE => None,
+            inject::FAULT::ERROR(errno) => Some(-(errno as i64)),
+            inject::FAULT::RETVAL(v) => Some(v),
+        }
+    }
+
+    /// Delay injected to the stop, the tracee shall be kept stopped before output
+    /// # Arguments
+    /// * `pid` - A thread ID stopped at syscall-entry or syscall-exit
+    /// * `e` - Syscall summery
+    pub fn injected_delay(&self, pid: types::Pid, e: &peek::SyscallSummery) -> Option<std::time::Duration> {
+        self.inject.borrow().delay(pid, e.is_entry())
+    }
+
+    /// Check whether fd table is required, for fd path annotation, path filter or dump files per opened fd
+    fn is_fd_tracked(&self) -> bool {
+        self.opts.fd_path || !self.filters.paths.is_empty() || !self.dump.borrow().is_empty()
+    }
+
     /// Set default value as skip output
     pub fn set_skip_for_default(&mut self) {
         self.conf.set_skip_for_default()
@@ -841,29 +1578,29 @@ impl Printer {
 
     /// Set to skip output for specified name's syscall
     /// # Arguments
-    /// * `name` - Target syscall's name
-    pub fn set_skip_by_name(&mut self, name: &str) {
+    /// * `name` - Target syscall's name, "%class", "/regex/", or "!" prefixed to select syscalls not selected by the rest
+    pub fn set_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.conf.set_skip_by_name(name)
     }
 
     /// Set to not skip output for specified name's syscall
     /// # Arguments
-    /// * `name` - Target syscall's name
-    pub fn set_not_skip_by_name(&mut self, name: &str) {
+    /// * `name` - Target syscall's name, "%class", "/regex/", or "!" prefixed to select syscalls not selected by the rest
+    pub fn set_not_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.conf.set_not_skip_by_name(name)
     }
 
     /// Set to simple format output for specified name's syscall
     /// # Arguments
-    /// * `name` - Target syscall's name
-    pub fn set_simple_by_name(&mut self, name: &str) {
+    /// * `name` - Target syscall's name, "%class", "/regex/", or "!" prefixed to select syscalls not selected by the rest
+    pub fn set_simple_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.conf.set_simple_by_name(name)
     }
 
     /// Set to nopeek format output for specified name's syscall
     /// # Arguments
-    /// * `name` - Target syscall's name
-    pub fn set_nopeek_by_name(&mut self, name: &str) {
+    /// * `name` - Target syscall's name, "%class", "/regex/", or "!" prefixed to select syscalls not selected by the rest
+    pub fn set_nopeek_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.conf.set_nopeek_by_name(name)
     }
 
@@ -895,6 +1632,254 @@ impl Printer {
         self.conf.set_nopeek_by_include_name(name)
     }
 
+    /// Set timestamp kind printed at head of log line
+    /// # Arguments
+    /// * `timestamp` - Timestamp kind
+    pub fn set_timestamp(&mut self, timestamp: TIMESTAMP) {
+        self.opts.timestamp = timestamp;
+    }
+
+    /// Set trace start of recorded trace, used for timestamps instead of the current trace start
+    /// # Arguments
+    /// * `wall` - Wall clock at the trace start
+    /// * `mono` - Monotonic clock corresponding to the trace start
+    pub fn set_clock(&mut self, wall: std::time::SystemTime, mono: std::time::Instant) {
+        self.clock = time::Clock::at(wall, mono);
+    }
+
+    /// Get wall clock and monotonic clock at trace start, base of timestamps
+    pub fn clock_start(&self) -> (std::time::SystemTime, std::time::Instant) {
+        self.clock.start()
+    }
+
+    /// Set time of recorded event to output, None to use the current time
+    pub fn set_event_time(&self, t: Option<std::time::Instant>) {
+        self.event_time.set(t);
+    }
+
+    /// Set to print time spent in syscall at tail of syscall-exit log line
+    pub fn set_duration(&mut self) {
+        self.opts.duration = true;
+    }
+
+    /// Set to collect per syscall statistics
+    /// # Arguments
+    /// * `only` - Not print each syscall, print only summary
+    pub fn set_summary(&mut self, only: bool) {
+        self.summary = Some(summary::Summary::new());
+        self.opts.quiet = only;
+    }
+
+    /// Output summary of collected statistics, do nothing if not collected
+    pub fn output_summary(&self) -> std::result::Result<(), std::io::Error> {
+        self.writer.select(None);
+        match &self.summary {
+            Some(s) => s.write(self),
+            None => Ok(()),
+        }
+    }
+
+    /// Set log output format
+    /// # Arguments
+    /// * `output` - Output format
+    pub fn set_output(&mut self, output: OUTPUT) {
+        self.opts.output = output;
+    }
+
+    /// Notify that traced process exited, output syscall that never returned, e.g. exit_group
+    /// # Arguments
+    /// * `pid` - A process ID of exited process
+    pub fn exit_process(&self, pid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
+        self.path_skipped.borrow_mut().remove(&pid);
+        self.inject.borrow_mut().exit_process(pid);
+        let line = self.pending.borrow_mut().remove(&pid);
+        match line {
+            // result of unfinished syscall is never known
+            Some(_) if self.is_ret_filtered() => Ok(()),
+            Some(head) if self.is_json() => {
+                self.write(&head)?;
+                self.write(b", ")?;
+                self.write_key("unfinished")?;
+                self.write(b"true}")?;
+                self.flush_line()
+            },
+            Some(line) => {
+                self.write(&line)?;
+                self.write(b" <unfinished ...>")?;
+                self.flush_line()
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Output signal delivered to traced process
+    /// # Arguments
+    /// * `pid` - A process ID of signal delivered process
+    /// * `sig` - Delivered signal number
+    /// * `info` - siginfo_t of the signal
+    pub fn output_signal(&self, pid: types::Pid, sig: types::SInt, info: &peek::SignalInfo) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
+        if self.opts.quiet {
+            return Ok(());
+        }
+        let e = Self::native_summery();
+        self.write_event_header(pid, "signal")?;
+        if self.is_json() {
+            self.write(b", ")?;
+            self.write_key("signal")?;
+            signal::write_signal(self, sig as u64, &e)?;
+            self.write(b", ")?;
+            self.write_key("siginfo")?;
+        } else {
+            self.write(b"--- ")?;
+            signal::write_signal(self, sig as u64, &e)?;
+            self.write(b" ")?;
+        }
+        if cfg!(target_pointer_width = "64") {
+            self.write_struct_from_buf::<signal::siginfo>(info.as_bytes(), pid, &e)?;
+        } else {
+            self.write_struct_from_buf::<signal::compat_siginfo>(info.as_bytes(), pid, &e)?;
+        }
+        self.write(if self.is_json() { b"}" } else { b" ---" })?;
+        self.flush_line()
+    }
+
+    /// Output exit status of traced process, call after `exit_process`
+    /// # Arguments
+    /// * `pid` - A process ID of exited process
+    /// * `status` - Exit status
+    pub fn output_exited(&self, pid: types::Pid, status: types::SInt) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
+        if !self.opts.quiet {
+            self.write_event_header(pid, "exited")?;
+            if self.is_json() {
+                self.write(b", ")?;
+                self.write_key("status")?;
+                self.write_number(status, &FORMATS::DEC)?;
+                self.write(b"}")?;
+            } else {
+                self.write(b"+++ exited with ")?;
+                self.write_number(status, &FORMATS::DEC)?;
+                self.write(b" +++")?;
+            }
+            self.flush_line()?;
+        }
+        if self.get_tgid(pid) == pid {
+            self.writer.close_process(pid)?;
+            self.dump.borrow_mut().close_process(pid);
+        }
+        self.tgid.borrow_mut().remove(&pid);
+        self.fds.borrow_mut().exit(pid);
+        Ok(())
+    }
+
+    /// Output signal that killed traced process, call after `exit_process`
+    /// # Arguments
+    /// * `pid` - A process ID of killed process
+    /// * `sig` - Signal number killed the process
+    /// * `core` - Core dumped
+    pub fn output_killed(&self, pid: types::Pid, sig: types::SInt, core: bool) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
+        if !self.opts.quiet {
+            let e = Self::native_summery();
+            self.write_event_header(pid, "killed")?;
+            if self.is_json() {
+                self.write(b", ")?;
+                self.write_key("signal")?;
+                signal::write_signal(self, sig as u64, &e)?;
+                self.write(b", ")?;
+                self.write_key("core_dumped")?;
+                self.write(if core { b"true" } else { b"false" })?;
+                self.write(b"}")?;
+            } else {
+                self.write(b"+++ killed by ")?;
+                signal::write_signal(self, sig as u64, &e)?;
+                if core {
+                    self.write(b" (core dumped)")?;
+                }
+                self.write(b" +++")?;
+            }
+            self.flush_line()?;
+        }
+        if self.get_tgid(pid) == pid {
+            self.writer.close_process(pid)?;
+            self.dump.borrow_mut().close_process(pid);
+        }
+        self.tgid.borrow_mut().remove(&pid);
+        self.fds.borrow_mut().exit(pid);
+        Ok(())
+    }
+
+    /// Output new command name of traced process executed new program
+    /// # Arguments
+    /// * `pid` - A process ID of executed process
+    /// * `former` - A thread ID called execve, differ from pid if non-leader thread called
+    pub fn output_exec(&self, pid: types::Pid, former: types::Pid) -> std::result::Result<(), std::io::Error> {
+        self.select_output(pid);
+        if self.opts.quiet {
+            return Ok(());
+        }
+        let comm = peek::get_comm(pid).unwrap_or_default();
+        self.write_event_header(pid, "exec")?;
+        if self.is_json() {
+            self.write(b", ")?;
+            self.write_key("comm")?;
+            self.write_str(&comm)?;
+            if former != pid {
+                self.write(b", ")?;
+                self.write_key("former_tid")?;
+                self.write_number(former, &FORMATS::DEC)?;
+            }
+            self.write(b"}")?;
+        } else {
+            self.write(b"+++ exec ")?;
+            self.write_str(&comm)?;
+            if former != pid {
+                self.write(b" by former tid ")?;
+                self.write_number(former, &FORMATS::DEC)?;
+            }
+            self.write(b" +++")?;
+        }
+        self.flush_line()
+    }
+
+    /// Set to print fd with path or socket description, e.g. 3</etc/passwd>
+    pub fn set_fd_path(&mut self) {
+        self.opts.fd_path = true;
+    }
+
+    /// Add path to output only syscalls touching the path, or fd opened from the path
+    /// # Arguments
+    /// * `path` - A path of file or directory, syscalls touching files under the directory are also output
+    pub fn add_path_filter(&mut self, path: &str) {
+        self.filters.paths.add(path)
+    }
+
+    /// Set to output only syscalls of the result, syscall-entry line is output at syscall-exit
+    pub fn set_result_filter(&mut self, result: RESULT) {
+        self.filters.result = result;
+    }
+
+    /// Add errno to output only syscalls failed with it, syscall-entry line is output at syscall-exit
+    /// # Arguments
+    /// * `name` - An errno name e.g. ENOENT, or number
+    pub fn add_errno_filter(&mut self, name: &str) -> bool {
+        match errno::errno_value(name).or_else(|| name.parse::<u32>().ok()) {
+            Some(v) => {
+                self.filters.errnos.push(v as i32);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Set to output syscall-entry and syscall-exit as one line,
+    /// split into unfinished and resumed lines only if other process's syscall interleaves
+    pub fn set_merge(&mut self) {
+        self.opts.merge = true;
+    }
+
     /// Set log destinaion
     /// # Arguments
     /// * `path` - file path for log destinaion
@@ -902,4 +1887,61 @@ impl Printer {
         self.writer = logger::Logger::file(path);
     }
 
+    /// Set log destinaion to file per process, `<path>.<pid>`
+    /// # Arguments
+    /// * `path` - file path prefix for log destinaion
+    pub fn file_separately(&mut self, path: String) {
+        self.writer = logger::Logger::file_separately(path);
+    }
+
+    /// Notify that new process or thread is created, open log destination if output separately
+    /// # Arguments
+    /// * `pid` - A process ID of new process or thread
+    pub fn start_process(&self, pid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        // not cached, new thread may already exit
+        if peek::get_tgid(pid).is_ok_and(|tgid| tgid == pid) {
+            self.writer.open_process(pid)?;
+        }
+        Ok(())
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn openat(ret: i64) -> peek::SyscallSummery {
+        peek::SyscallSummery::new_dummy_entry(true, arch::sys_uni::NR::sys_openat, 257, [0; 6], ret)
+    }
+
+    #[test]
+    fn ret_matched_any() {
+        let printer = Printer::new();
+        assert!(printer.is_ret_matched(&openat(3)));
+        assert!(printer.is_ret_matched(&openat(-2)));
+    }
+
+    #[test]
+    fn ret_matched_result() {
+        let mut printer = Printer::new();
+        printer.set_result_filter(RESULT::SUCCESS);
+        assert!(printer.is_ret_matched(&openat(3)));
+        assert!(!printer.is_ret_matched(&openat(-2)));
+        printer.set_result_filter(RESULT::FAILED);
+        assert!(!printer.is_ret_matched(&openat(3)));
+        assert!(printer.is_ret_matched(&openat(-2)));
+    }
+
+    #[test]
+    fn ret_matched_errno() {
+        let mut printer = Printer::new();
+        assert!(printer.add_errno_filter("ENOENT"));
+        assert!(printer.add_errno_filter("13"));
+        assert!(!printer.add_errno_filter("ENOSUCH"));
+        assert!(!printer.is_ret_matched(&openat(3)));
+        assert!(printer.is_ret_matched(&openat(-2)));
+        assert!(printer.is_ret_matched(&openat(-13)));
+        assert!(!printer.is_ret_matched(&openat(-17)));
+    }
 }
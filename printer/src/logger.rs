@@ -20,26 +20,123 @@ impl std::io::Write for LogWriter {
     }
 }
 
+/// Writer key of log not related to traced process, e.g. summary
+const NO_PROCESS: types::Pid = 0;
+
 pub struct Logger {
-    writer: std::cell::RefCell<std::io::BufWriter<LogWriter>>,
+    /// Writers for each process ID, only `NO_PROCESS` is used if not output separately
+    writers: std::cell::RefCell<std::collections::BTreeMap<types::Pid, std::io::BufWriter<LogWriter>>>,
+    /// File path, output to stdout if None
+    path: Option<String>,
+    /// Output to file `<path>.<pid>` per process
+    separately: bool,
+    /// Process ID of current output destination
+    current: std::cell::Cell<types::Pid>,
+    /// Process IDs of opened files, reopened file is appended
+    opened: std::cell::RefCell<std::collections::BTreeSet<types::Pid>>,
+    capture: std::cell::RefCell<Vec<Vec<u8>>>,
 }
 
 impl Logger {
+    fn new(path: Option<String>, separately: bool) -> Self {
+        Self{writers: std::cell::RefCell::new(std::collections::BTreeMap::new()), path, separately, current: std::cell::Cell::new(NO_PROCESS), opened: std::cell::RefCell::new(std::collections::BTreeSet::new()), capture: std::cell::RefCell::new(vec![])}
+    }
+
+    fn open(&self, pid: types::Pid) -> std::result::Result<std::io::BufWriter<LogWriter>, std::io::Error> {
+        let path = match &self.path {
+            None => return Ok(std::io::BufWriter::new(LogWriter::DEFAULT(std::io::stdout()))),
+            Some(path) if self.separately && pid != NO_PROCESS => format!("{}.{}", path, pid),
+            Some(path) => path.clone(),
+        };
+        // events of new process may be reported before its creation, and pid may be reused
+        let first = !self.opened.borrow().contains(&pid);
+        let file = std::fs::File::options().write(true).create(true).truncate(first).append(!first).open(&path)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", path, e)))?;
+        self.opened.borrow_mut().insert(pid);
+        Ok(std::io::BufWriter::new(LogWriter::FILE(file)))
+    }
+
+    fn with_writer<T, F: FnOnce(&mut std::io::BufWriter<LogWriter>) -> std::result::Result<T, std::io::Error>>(&self, f: F) -> std::result::Result<T, std::io::Error> {
+        let pid = self.current.get();
+        let mut writers = self.writers.borrow_mut();
+        let w = match writers.entry(pid) {
+            std::collections::btree_map::Entry::Occupied(o) => o.into_mut(),
+            std::collections::btree_map::Entry::Vacant(v) => v.insert(self.open(pid)?),
+        };
+        f(w)
+    }
+
     pub fn write(&self, buf: &[u8]) -> std::result::Result<usize, std::io::Error> {
-        self.writer.borrow_mut().write(buf)
+        if let Some(c) = self.capture.borrow_mut().last_mut() {
+            c.extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+        self.with_writer(|w| w.write(buf))
+    }
+
+    /// Start to capture written data instead of output, capture can be nested
+    pub fn begin_capture(&self) {
+        self.capture.borrow_mut().push(vec![]);
+    }
+
+    /// Stop to capture and get captured data
+    pub fn end_capture(&self) -> Vec<u8> {
+        self.capture.borrow_mut().pop().unwrap_or_default()
     }
 
     pub fn flush_line(&self) -> std::result::Result<(), std::io::Error> {
-        let mut writer = self.writer.borrow_mut();
-        writer.write(b"\n")?;
-        writer.flush()
+        self.with_writer(|w| {
+            w.write(b"\n")?;
+            w.flush()
+        })
+    }
+
+    /// Select output destination, no effect if not output separately
+    /// # Arguments
+    /// * `tgid` - A process ID of output target, None for log not related to traced process
+    /// * return - Previous output destination, for restore selection
+    pub fn select(&self, tgid: Option<types::Pid>) -> Option<types::Pid> {
+        let prev = Some(self.current.get()).filter(|p| *p != NO_PROCESS);
+        if self.separately {
+            self.current.set(tgid.unwrap_or(NO_PROCESS));
+        }
+        prev
+    }
+
+    /// Open output destination for new process, no effect if not output separately
+    /// # Arguments
+    /// * `tgid` - A process ID of new process
+    pub fn open_process(&self, tgid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        if self.separately && !self.writers.borrow().contains_key(&tgid) {
+            let w = self.open(tgid)?;
+            self.writers.borrow_mut().insert(tgid, w);
+        }
+        Ok(())
+    }
+
+    /// Close output destination of exited process, no effect if not output separately
+    /// # Arguments
+    /// * `tgid` - A process ID of exited process
+    pub fn close_process(&self, tgid: types::Pid) -> std::result::Result<(), std::io::Error> {
+        if !self.separately {
+            return Ok(());
+        }
+        match self.writers.borrow_mut().remove(&tgid) {
+            Some(mut w) => w.flush(),
+            None => Ok(()),
+        }
     }
 
     pub fn default() -> Self {
-        Self{writer: std::cell::RefCell::new(std::io::BufWriter::new(LogWriter::DEFAULT(std::io::stdout())))}
+        Self::new(None, false)
     }
 
     pub fn file(path: String) -> Self {
-        Self{writer: std::cell::RefCell::new(std::io::BufWriter::new(LogWriter::FILE(std::fs::File::options().write(true).create(true).truncate(true).open(path).unwrap())))}
+        Self::new(Some(path), false)
+    }
+
+    /// Output to file `<path>.<pid>` per process, threads of process share the file
+    pub fn file_separately(path: String) -> Self {
+        Self::new(Some(path), true)
     }
 }
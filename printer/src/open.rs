@@ -2,22 +2,17 @@ use crate::FORMATS;
 
 const AT_FLAG: [(u32, &'static str); 5] = [ (0x100,"AT_SYMLINK_NOFOLLOW"), (0x200,"AT_REMOVEDIR"), (0x400,"AT_SYMLINK_FOLLOW"), (0x800,"AT_NO_AUTOMOUNT"), (0x1000,"AT_EMPTY_PATH"),];
 
-pub fn write_dir_fd(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-    let value = value as arch::types::a64::SInt;
-    if value == -100 {
-        printer.write(b"AT_FDCWD")
+pub fn write_dir_fd(printer: &crate::Printer, value: u64, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    if value as arch::types::a64::SInt == -100 {
+        printer.write_text(b"AT_FDCWD")
     } else {
-        printer.write_number(value, &FORMATS::DEC)
+        crate::fd::write_fd(printer, value, pid, e)
     }
 }
 
 pub fn write_at_flags(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
-    if value == 0 {
-        printer.write(b"0")
-    } else {
-        printer.write_mask_enum(value, &AT_FLAG)
-    }
+    printer.write_mask_enum(value, &AT_FLAG)
 }
 
 const OPEN_MODE: [(u32, &'static str); 4] = [ (0o00000003, "O_ACCMODE"), (0o00000000, "O_RDONLY"), (0o00000001, "O_WRONLY"), (0o00000002, "O_RDWR"), ];
@@ -30,13 +25,10 @@ const O_FLAG: [(u32, &'static str); 14] = [
 pub fn write_open_flags(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
     let mode = value & 0x3;
+    printer.write_flags_begin()?;
     printer.write_enum(mode, &OPEN_MODE)?;
-    let mode = value & !0x3;
-    if mode != 0 {
-        printer.write(b" | ")?;
-        printer.write_mask_enum(mode, &O_FLAG)?;
-    }
-    Ok(())
+    printer.write_mask_enum_items(value & !0x3, &O_FLAG, true)?;
+    printer.write_flags_end()
 }
 
 pub fn write_fd_flags(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
@@ -58,11 +50,7 @@ pub fn write_lseek_whence(printer: &crate::Printer, value: u64, _: &peek::Syscal
 const ACCESS_AT_FLAG: [(u32, &'static str); 2] = [ (0x100,"AT_SYMLINK_NOFOLLOW"), (0x200,"AT_EACCESS"), ];
 pub fn write_accessat_flags(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
-    if value == 0 {
-        printer.write(b"0")
-    } else {
-        printer.write_mask_enum(value, &ACCESS_AT_FLAG)
-    }
+    printer.write_mask_enum(value, &ACCESS_AT_FLAG)
 }
 
 const RENAME_FLAG: [(u32, &'static str); 3] = [ (1 << 0, "RENAME_NOREPLACE"), (1 << 1, "RENAME_EXCHANGE"), (1 << 2, "RENAME_WHITEOUT"),];
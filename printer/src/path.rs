@@ -0,0 +1,289 @@
+use crate::TYPES;
+use std::os::unix::ffi::OsStringExt;
+
+const AT_FDCWD: types::SInt = -100;
+
+/// Paths given to filter syscalls, syscall touching the path or under the path is output
+pub struct PathFilter {
+    /// Paths as given and its absolute path
+    paths: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+/// Check whether `path` is same as `prefix` or under directory `prefix`
+fn is_under(path: &[u8], prefix: &[u8]) -> bool {
+    if prefix.is_empty() || !path.starts_with(prefix) {
+        return false;
+    }
+    path.len() == prefix.len() || prefix.ends_with(b"/") || path[prefix.len()] == b'/'
+}
+
+/// Match shell wildcard pattern, `*`, `?` and `[...]` are supported
+pub fn glob(p: &[u8], s: &[u8]) -> bool {
+    let (mut pi, mut si) = (0, 0);
+    // pattern after the last `*` and position of `s` matched to it, retried from the next position on mismatch
+    let mut retry = None;
+    loop {
+        if p.get(pi) == Some(&b'*') {
+            pi += 1;
+            retry = Some((pi, si));
+            continue;
+        }
+        if pi == p.len() && si == s.len() {
+            return true;
+        }
+        if let Some(len) = s.get(si).and_then(|c| glob_one(&p[pi..], *c)) {
+            pi += len;
+            si += 1;
+            continue;
+        }
+        match retry {
+            Some((rp, rs)) if rs < s.len() => {
+                retry = Some((rp, rs + 1));
+                pi = rp;
+                si = rs + 1;
+            },
+            _ => return false,
+        }
+    }
+}
+
+/// Match a character to the head element of pattern, return length of the element if matched
+fn glob_one(p: &[u8], c: u8) -> Option<usize> {
+    match p.split_first()? {
+        (b'?', _) => Some(1),
+        (b'[', rest) => match glob_set(rest, c) {
+            Some((len, matched)) => matched.then_some(len + 1),
+            // "[" without "]" is literal
+            None => (c == b'[').then_some(1),
+        },
+        (x, _) => (*x == c).then_some(1),
+    }
+}
+
+/// Match a character to set following "[", return length of the set including "]" and whether matched
+fn glob_set(p: &[u8], c: u8) -> Option<(usize, bool)> {
+    let negate = matches!(p.first(), Some(b'!') | Some(b'^'));
+    let start = negate as usize;
+    // "]" just after "[" or "[!" is a member of the set
+    let end = start + 1 + p.get(start + 1..)?.iter().position(|x| *x == b']')?;
+    let set = &p[start..end];
+    let mut matched = false;
+    let mut i = 0;
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == b'-' {
+            matched |= set[i] <= c && c <= set[i + 2];
+            i += 3;
+        } else {
+            matched |= set[i] == c;
+            i += 1;
+        }
+    }
+    Some((end + 1, matched != negate))
+}
+
+/// Join relative path to base directory, and remove "." and ".." lexically
+fn normalize(base: &[u8], path: &[u8]) -> Vec<u8> {
+    let mut names: Vec<&[u8]> = vec![];
+    let full = if path.starts_with(b"/") { [path].concat() } else { [base, b"/", path].concat() };
+    for name in full.split(|c| *c == b'/') {
+        match name {
+            b"" | b"." => {},
+            b".." => { names.pop(); },
+            n => names.push(n),
+        }
+    }
+    let mut r = vec![];
+    for name in names {
+        r.push(b'/');
+        r.extend_from_slice(name);
+    }
+    if r.is_empty() { r.push(b'/') }
+    r
+}
+
+/// Resolve path argument to absolute path lexically
+/// # Arguments
+/// * `pid` - A thread ID called syscall
+/// * `dir` - Directory fd argument for relative path, cwd if None or AT_FDCWD
+/// * `path` - A path argument
+pub(crate) fn resolve(pid: types::Pid, dir: Option<u64>, path: &[u8]) -> Option<Vec<u8>> {
+    let base = match dir {
+        _ if path.starts_with(b"/") => Some(vec![]),
+        Some(fd) if fd as types::SInt != AT_FDCWD => peek::get_fd_link(pid, fd as types::SInt).ok(),
+        _ => peek::get_cwd(pid).ok(),
+    };
+    base.map(|base| normalize(&base, path))
+}
+
+/// Absolute path of path given by user without resolving symbolic link
+pub(crate) fn lexical_absolute(path: &str) -> Vec<u8> {
+    let cwd = std::env::current_dir().map(|p| p.into_os_string().into_vec()).unwrap_or_default();
+    normalize(&cwd, path.as_bytes())
+}
+
+/// Absolute path of path given by user, symbolic links are resolved if exists
+pub(crate) fn absolute(path: &str) -> Vec<u8> {
+    match std::fs::canonicalize(path) {
+        Ok(p) => p.into_os_string().into_vec(),
+        Err(_) => lexical_absolute(path),
+    }
+}
+
+impl PathFilter {
+    pub fn new() -> Self {
+        PathFilter{ paths: vec![] }
+    }
+
+    /// Add path to filter
+    /// # Arguments
+    /// * `path` - A path of file or directory
+    pub fn add(&mut self, path: &str) {
+        self.paths.push((path.as_bytes().to_vec(), absolute(path)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    fn is_match_abs(&self, path: &[u8]) -> bool {
+        self.paths.iter().any(|(_, abs)| is_under(path, abs))
+    }
+
+    fn is_match_str(&self, pid: types::Pid, dir: Option<u64>, path: &[u8]) -> bool {
+        if self.paths.iter().any(|(raw, _)| is_under(path, raw)) {
+            return true;
+        }
+        resolve(pid, dir, path).is_some_and(|p| self.is_match_abs(&p))
+    }
+
+    /// Check whether path string or fd in syscall arguments refers the paths
+    /// # Arguments
+    /// * `printer` - Printer has fd table
+    /// * `conf` - Print types of syscall arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `e` - Syscall summery at syscall-entry
+    pub fn is_match(&self, printer: &crate::Printer, conf: &crate::config::SyscallPrintConf, pid: types::Pid, e: &peek::SyscallSummery) -> bool {
+        let print = conf.get_print_info(e.is_64());
+        let a = e.args();
+        let mut dir = None;
+        for i in 0..a.len() {
+            match print.args[i] {
+                TYPES::NONE => break,
+                TYPES::DirFd if a[i] as types::SInt == AT_FDCWD => dir = Some(a[i]),
+                TYPES::Fd | TYPES::DirFd => {
+                    dir = Some(a[i]);
+                    let fd = a[i] as types::SInt;
+                    if fd >= 0 && printer.fds.borrow().describe(pid, printer.get_tgid(pid), fd).is_some_and(|p| self.is_match_abs(&p)) {
+                        return true;
+                    }
+                },
+                TYPES::StrPtr => {
+                    if a[i] == 0 {
+                        continue;
+                    }
+                    if let Ok(path) = peek::peek_until_null(pid, a[i] as types::Ptr) {
+                        if self.is_match_str(pid, dir.take(), &path) {
+                            return true;
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_path() {
+        assert_eq!(normalize(b"/home/user", b"a/b"), b"/home/user/a/b");
+        assert_eq!(normalize(b"/home/user", b"/etc/passwd"), b"/etc/passwd");
+        assert_eq!(normalize(b"/home/user", b"./a/../b/"), b"/home/user/b");
+        assert_eq!(normalize(b"/home/user", b"../../.."), b"/");
+        assert_eq!(normalize(b"/", b"a//b/."), b"/a/b");
+        assert_eq!(normalize(b"/home/user", b""), b"/home/user");
+        assert_eq!(normalize(b"", b"a"), b"/a");
+    }
+
+    #[test]
+    fn resolve_path() {
+        let pid = std::process::id() as types::Pid;
+        let cwd = std::env::current_dir().unwrap().into_os_string().into_vec();
+        assert_eq!(resolve(pid, None, b"/etc/../etc/passwd").as_deref(), Some(&b"/etc/passwd"[..]));
+        assert_eq!(resolve(pid, Some(AT_FDCWD as u64), b"a/../b"), Some(normalize(&cwd, b"b")));
+        assert_eq!(resolve(pid, None, b"b"), Some(normalize(&cwd, b"b")));
+        let dir = std::fs::File::open("/").unwrap();
+        assert_eq!(resolve(pid, Some(std::os::fd::AsRawFd::as_raw_fd(&dir) as u64), b"tmp/x").as_deref(), Some(&b"/tmp/x"[..]));
+        assert_eq!(resolve(pid, Some(-1i32 as u32 as u64), b"x"), None);
+    }
+
+    #[test]
+    fn under_path() {
+        assert!(is_under(b"/etc", b"/etc"));
+        assert!(is_under(b"/etc/passwd", b"/etc"));
+        assert!(is_under(b"/etc/passwd", b"/etc/"));
+        assert!(is_under(b"/etc/passwd", b"/"));
+        assert!(!is_under(b"/etcetera", b"/etc"));
+        assert!(!is_under(b"/et", b"/etc"));
+        assert!(!is_under(b"/etc", b""));
+    }
+
+    #[test]
+    fn glob_literal() {
+        assert!(glob(b"", b""));
+        assert!(!glob(b"", b"a"));
+        assert!(glob(b"/var/log", b"/var/log"));
+        assert!(!glob(b"/var/log", b"/var/logs"));
+        assert!(glob(b"a?c", b"abc"));
+        assert!(!glob(b"a?c", b"ac"));
+    }
+
+    #[test]
+    fn glob_star() {
+        assert!(glob(b"*", b""));
+        assert!(glob(b"socket:*", b"socket:[1234]"));
+        assert!(glob(b"/var/log/*", b"/var/log/syslog"));
+        assert!(!glob(b"/var/log/*", b"/var/lib/dpkg"));
+        assert!(glob(b"*.log", b"a.log.log"));
+        assert!(!glob(b"*.log", b"a.log.gz"));
+        assert!(glob(b"a*b*c", b"aXbYbZc"));
+        assert!(!glob(b"a*b*c", b"aXbYbZ"));
+        assert!(glob(b"**a", b"ba"));
+        // backtracking is linear, not exponential in number of `*`
+        let s = [b'a'; 200];
+        assert!(!glob(b"*a*a*a*a*a*a*a*a*a*a*b", &s));
+    }
+
+    #[test]
+    fn glob_set() {
+        assert!(glob(b"[abc]", b"b"));
+        assert!(!glob(b"[abc]", b"d"));
+        assert!(glob(b"[a-c]x", b"cx"));
+        assert!(!glob(b"[a-c]x", b"dx"));
+        assert!(glob(b"[!a-c]", b"d"));
+        assert!(!glob(b"[!a-c]", b"a"));
+        assert!(glob(b"[^a-c]", b"d"));
+        assert!(glob(b"[a-]", b"-"));
+        assert!(glob(b"[0-9][0-9]", b"42"));
+        assert!(glob(b"*[0-9]", b"tty7"));
+    }
+
+    #[test]
+    fn glob_set_bracket() {
+        // "]" just after "[" or "[!" is a member
+        assert!(glob(b"[]]", b"]"));
+        assert!(glob(b"[]a]", b"a"));
+        assert!(glob(b"[!]]", b"a"));
+        assert!(!glob(b"[!]]", b"]"));
+        // unterminated set is literal
+        assert!(glob(b"[]", b"[]"));
+        assert!(!glob(b"[]", b"]"));
+        assert!(glob(b"[!]", b"[!]"));
+        assert!(!glob(b"[!]", b"a"));
+        assert!(glob(b"[a", b"[a"));
+    }
+}
@@ -16,9 +16,9 @@ const EVENTS: [(types::SShrt, &'static str); 13] = [
 
 impl crate::Print for pollfd {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".fd = ")?; printer.write_number(self.fd, &FORMATS::DEC)?;
-        printer.write(b", .events = ")?; printer.write_mask_enum(self.events, &EVENTS)?;
-        printer.write(b", .revents = ")?; printer.write_mask_enum(self.revents, &EVENTS)?;
+        printer.write_member("fd")?; printer.write_number(self.fd, &FORMATS::DEC)?;
+        printer.write_next_member("events")?; printer.write_mask_enum(self.events, &EVENTS)?;
+        printer.write_next_member("revents")?; printer.write_mask_enum(self.revents, &EVENTS)?;
         Ok(())
     }
 }
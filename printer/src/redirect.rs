@@ -0,0 +1,77 @@
+//! Path argument redirection, e.g. `/etc/resolv.conf=/tmp/test-resolv.conf`
+use crate::TYPES;
+
+struct Rule {
+    /// Path as given
+    raw: Vec<u8>,
+    /// Lexical absolute path and canonical path, tracee's path is resolved lexically
+    abs: [Vec<u8>; 2],
+    to: Vec<u8>,
+}
+
+/// Rules to replace path arguments of syscalls
+pub struct PathRedirect {
+    rules: Vec<Rule>,
+}
+
+impl PathRedirect {
+    pub fn new() -> Self {
+        PathRedirect{ rules: vec![] }
+    }
+
+    /// Add rule by `FROM=TO`
+    pub fn add(&mut self, spec: &str) -> std::result::Result<(), std::io::Error> {
+        let Some((from, to)) = spec.split_once('=').filter(|(f, t)| !f.is_empty() && !t.is_empty()) else {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("FROM=TO is required: {}", spec)));
+        };
+        let abs = [crate::path::lexical_absolute(from), crate::path::absolute(from)];
+        self.rules.push(Rule{ raw: from.as_bytes().to_vec(), abs, to: to.as_bytes().to_vec() });
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    fn find(&self, pid: types::Pid, dir: Option<u64>, path: &[u8]) -> Option<&[u8]> {
+        if let Some(r) = self.rules.iter().find(|r| r.raw == path) {
+            return Some(&r.to);
+        }
+        let abs = crate::path::resolve(pid, dir, path)?;
+        self.rules.iter().find(|r| r.abs.contains(&abs)).map(|r| r.to.as_slice())
+    }
+
+    /// Check whether syscall may take path, it shall be stopped by seccomp filter
+    pub fn is_target(&self, nr: arch::sys_uni::NR) -> bool {
+        !self.is_empty() && nr.class() & arch::sys_uni::class::FILE != 0
+    }
+
+    /// Find path arguments to replace, return argument positions and replacement paths
+    /// # Arguments
+    /// * `pid` - A thread ID called syscall
+    /// * `e` - Syscall summery at syscall-entry
+    pub fn redirect(&self, pid: types::Pid, e: &peek::SyscallSummery) -> Vec<(usize, Vec<u8>)> {
+        if !self.is_target(e.uni_sysnum()) {
+            return vec![];
+        }
+        let conf = crate::config::SyscallPrintConf::new_print(e.uni_sysnum());
+        let print = conf.get_print_info(e.is_64());
+        let a = e.args();
+        let mut dir = None;
+        let mut r = vec![];
+        for i in 0..a.len() {
+            match print.args[i] {
+                TYPES::NONE => break,
+                TYPES::DirFd => dir = Some(a[i]),
+                TYPES::StrPtr if a[i] != 0 => {
+                    let Ok(path) = peek::peek_until_null(pid, a[i] as types::Ptr) else { continue };
+                    if let Some(to) = self.find(pid, dir.take(), &path) {
+                        r.push((i, to.to_vec()));
+                    }
+                },
+                _ => {},
+            }
+        }
+        r
+    }
+}
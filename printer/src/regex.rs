@@ -0,0 +1,307 @@
+//! Small backtracking regular expression for selecting syscalls by name
+//! Supported: literal, `.`, `[...]`, `[^...]`, `^`, `$`, `(...)`, `|`, `*`, `+`, `?`, `{m}`, `{m,}`, `{m,n}`, `\d`, `\w`, `\s`
+
+enum Node {
+    Char(u8),
+    Any,
+    Class(Vec<(u8, u8)>, bool),
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+/// Upper bound of `{m,n}` count, each repeat recurses once in matching
+const REPEAT_MAX: usize = 255;
+
+pub struct Regex {
+    alt: Vec<Vec<Node>>,
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+fn escape_class(c: u8) -> Option<Vec<(u8, u8)>> {
+    match c {
+        b'd' => Some(vec![(b'0', b'9')]),
+        b'w' => Some(vec![(b'a', b'z'), (b'A', b'Z'), (b'0', b'9'), (b'_', b'_')]),
+        b's' => Some(vec![(b' ', b' '), (b'\t', b'\r')]),
+        _ => None,
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<u8, String> {
+        let c = self.peek().ok_or("unexpected end of pattern")?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn alt(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alt = vec![self.seq()?];
+        while self.peek() == Some(b'|') {
+            self.pos += 1;
+            alt.push(self.seq()?);
+        }
+        Ok(alt)
+    }
+
+    fn seq(&mut self) -> Result<Vec<Node>, String> {
+        let mut seq = vec![];
+        while let Some(c) = self.peek() {
+            if c == b'|' || c == b')' {
+                break;
+            }
+            let atom = self.atom()?;
+            seq.push(self.repeat(atom)?);
+        }
+        Ok(seq)
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let begin = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.s[begin..self.pos]).ok()?.parse().ok()
+    }
+
+    fn repeat(&mut self, atom: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some(b'*') => (0, None),
+            Some(b'+') => (1, None),
+            Some(b'?') => (0, Some(1)),
+            Some(b'{') => {
+                self.pos += 1;
+                let min = self.number().ok_or("invalid repeat count")?;
+                let max = if self.peek() == Some(b',') {
+                    self.pos += 1;
+                    self.number()
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some(b'}') {
+                    return Err("unclosed {".into());
+                }
+                if max.is_some_and(|max| max < min) {
+                    return Err("invalid repeat count".into());
+                }
+                if min.max(max.unwrap_or(0)) > REPEAT_MAX {
+                    return Err("repeat count too large".into());
+                }
+                (min, max)
+            },
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        if matches!(atom, Node::Start | Node::End) {
+            return Err("nothing to repeat".into());
+        }
+        Ok(Node::Repeat(Box::new(atom), min, max))
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negate = self.peek() == Some(b'^');
+        if negate {
+            self.pos += 1;
+        }
+        let mut ranges = vec![];
+        let mut first = true;
+        loop {
+            let c = self.next().map_err(|_| "unclosed [")?;
+            if c == b']' && !first {
+                break;
+            }
+            first = false;
+            if c == b'\\' {
+                let e = self.next()?;
+                match escape_class(e) {
+                    Some(mut r) => ranges.append(&mut r),
+                    None => ranges.push((e, e)),
+                }
+            } else if self.peek() == Some(b'-') && self.s.get(self.pos + 1).is_some_and(|x| *x != b']') {
+                self.pos += 1;
+                let end = self.next()?;
+                ranges.push((c, end));
+            } else {
+                ranges.push((c, c));
+            }
+        }
+        Ok(Node::Class(ranges, negate))
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        match self.next()? {
+            b'.' => Ok(Node::Any),
+            b'^' => Ok(Node::Start),
+            b'$' => Ok(Node::End),
+            b'[' => self.class(),
+            b'(' => {
+                let alt = self.alt()?;
+                if self.next().ok() != Some(b')') {
+                    return Err("unclosed (".into());
+                }
+                Ok(Node::Group(alt))
+            },
+            b'\\' => {
+                let e = self.next()?;
+                Ok(match escape_class(e) {
+                    Some(r) => Node::Class(r, false),
+                    None => Node::Char(e),
+                })
+            },
+            b'*' | b'+' | b'?' | b'{' => Err("nothing to repeat".into()),
+            c => Ok(Node::Char(c)),
+        }
+    }
+}
+
+/// Match nodes from `pos` then call continuation `k` with end position of match
+fn match_seq(nodes: &[Node], s: &[u8], pos: usize, k: &dyn Fn(usize) -> bool) -> bool {
+    let Some((node, rest)) = nodes.split_first() else {
+        return k(pos);
+    };
+    let next = |p: usize| match_seq(rest, s, p, k);
+    match node {
+        Node::Char(c) => s.get(pos) == Some(c) && next(pos + 1),
+        Node::Any => pos < s.len() && next(pos + 1),
+        Node::Class(ranges, negate) => {
+            s.get(pos).is_some_and(|c| ranges.iter().any(|(b, e)| b <= c && c <= e) != *negate) && next(pos + 1)
+        },
+        Node::Start => pos == 0 && next(pos),
+        Node::End => pos == s.len() && next(pos),
+        Node::Group(alt) => alt.iter().any(|seq| match_seq(seq, s, pos, &next)),
+        Node::Repeat(node, min, max) => match_repeat(node, *min, *max, s, pos, &next),
+    }
+}
+
+/// Match `node` greedily at least `min` and at most `max` times
+fn match_repeat(node: &Node, min: usize, max: Option<usize>, s: &[u8], pos: usize, k: &dyn Fn(usize) -> bool) -> bool {
+    if max == Some(0) {
+        return k(pos);
+    }
+    // empty match counts toward `min`, but not repeated after `min` to terminate
+    let more = |p: usize| (p != pos || min > 0) && match_repeat(node, min.saturating_sub(1), max.map(|m| m - 1), s, p, k);
+    match_seq(std::slice::from_ref(node), s, pos, &more) || (min == 0 && k(pos))
+}
+
+impl Regex {
+    /// Compile pattern
+    /// # Arguments
+    /// * `pattern` - A regular expression
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let mut parser = Parser{ s: pattern.as_bytes(), pos: 0 };
+        let alt = parser.alt()?;
+        if parser.pos != parser.s.len() {
+            return Err("unmatched )".into());
+        }
+        Ok(Regex{ alt })
+    }
+
+    /// Check whether the pattern matches any part of text
+    pub fn is_match(&self, text: &str) -> bool {
+        let s = text.as_bytes();
+        (0..=s.len()).any(|pos| self.alt.iter().any(|seq| match_seq(seq, s, pos, &|_| true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Regex;
+
+    fn is_match(pattern: &str, text: &str) -> bool {
+        Regex::new(pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn anchors() {
+        assert!(is_match("^read$", "read"));
+        assert!(!is_match("^read$", "pread"));
+        assert!(!is_match("^read$", "readv"));
+        assert!(is_match("read", "pread64"));
+        assert!(is_match("^p", "pread64"));
+        assert!(is_match("64$", "pread64"));
+        assert!(!is_match("^64", "pread64"));
+    }
+
+    #[test]
+    fn classes() {
+        assert!(is_match("^[a-c]+$", "abcba"));
+        assert!(!is_match("^[a-c]+$", "abcd"));
+        assert!(is_match("^[^0-9]+$", "read"));
+        assert!(!is_match("^[^0-9]+$", "read64"));
+        assert!(is_match("^[]a]$", "]"));
+        assert!(is_match("^[a-]$", "-"));
+        assert!(is_match("^\\d\\d$", "64"));
+        assert!(is_match("^\\w+$", "clock_gettime"));
+        assert!(!is_match("^\\w+$", "a b"));
+        assert!(is_match("^a\\sb$", "a b"));
+        assert!(is_match("^[\\d_]+$", "6_4"));
+        assert!(is_match("^a.c$", "abc"));
+        assert!(!is_match("^a.c$", "ac"));
+        assert!(is_match("^a\\.c$", "a.c"));
+        assert!(!is_match("^a\\.c$", "abc"));
+    }
+
+    #[test]
+    fn alternation() {
+        assert!(is_match("^(open|close)$", "open"));
+        assert!(is_match("^(open|close)$", "close"));
+        assert!(!is_match("^(open|close)$", "openat"));
+        assert!(is_match("^open|close$", "openat"));
+        assert!(is_match("^(p)?read(v|64)?$", "preadv"));
+        assert!(is_match("^(p)?read(v|64)?$", "read"));
+        assert!(!is_match("^(p)?read(v|64)?$", "readahead"));
+    }
+
+    #[test]
+    fn repeats() {
+        assert!(is_match("^a*$", "aaa"));
+        assert!(is_match("^a+b$", "aab"));
+        assert!(!is_match("^a+b$", "b"));
+        assert!(is_match("^a{2}$", "aa"));
+        assert!(!is_match("^a{2}$", "aaa"));
+        assert!(is_match("^a{2,}$", "aaaa"));
+        assert!(!is_match("^a{2,}$", "a"));
+        assert!(is_match("^a{1,2}b$", "aab"));
+        assert!(!is_match("^a{1,2}b$", "aaab"));
+        // greedy repeat gives back for the rest
+        assert!(is_match("^a*ab$", "aaab"));
+        assert!(is_match("^(ab)+$", "ababab"));
+        assert!(!is_match("^(ab)+$", "ababa"));
+    }
+
+    #[test]
+    fn empty_matches() {
+        assert!(is_match("", "read"));
+        assert!(is_match("^$", ""));
+        assert!(is_match("^a*$", ""));
+        assert!(is_match("^(a*){1}$", ""));
+        assert!(is_match("^(a*)+$", ""));
+        assert!(is_match("^(a*)*b$", "aab"));
+        assert!(is_match("^(a|)+$", "aa"));
+        assert!(is_match("^(a?){3}b$", "ab"));
+        assert!(!is_match("^(a*){1}$", "b"));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(Regex::new("(read").is_err());
+        assert!(Regex::new("read)").is_err());
+        assert!(Regex::new("[read").is_err());
+        assert!(Regex::new("*read").is_err());
+        assert!(Regex::new("a{x}").is_err());
+        assert!(Regex::new("a{2").is_err());
+        assert!(Regex::new("^*").is_err());
+        assert!(Regex::new("a{3,1}").is_err());
+        assert!(Regex::new("a{256}").is_err());
+        assert!(Regex::new("a{1,256}").is_err());
+        assert!(Regex::new("a{255}").is_ok());
+    }
+}
@@ -31,9 +31,9 @@ pub struct compat_rlimit {
 
 impl crate::Print for rlimit64 {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".rlim_cur = ")?;
+        printer.write_member("rlim_cur")?;
         printer.write_number(self.rlim_cur, &FORMATS::DEC)?;
-        printer.write(b", .rlim_max = ")?;
+        printer.write_next_member("rlim_max")?;
         printer.write_number(self.rlim_max, &FORMATS::DEC)?;
         Ok(())
     }
@@ -41,9 +41,9 @@ impl crate::Print for rlimit64 {
 
 impl crate::Print for rlimit {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".rlim_cur = ")?;
+        printer.write_member("rlim_cur")?;
         printer.write_number(self.rlim_cur, &FORMATS::DEC)?;
-        printer.write(b", .rlim_max = ")?;
+        printer.write_next_member("rlim_max")?;
         printer.write_number(self.rlim_max, &FORMATS::DEC)?;
         Ok(())
     }
@@ -51,9 +51,9 @@ impl crate::Print for rlimit {
 
 impl crate::Print for compat_rlimit {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".rlim_cur = ")?;
+        printer.write_member("rlim_cur")?;
         printer.write_number(self.rlim_cur, &FORMATS::DEC)?;
-        printer.write(b", .rlim_max = ")?;
+        printer.write_next_member("rlim_max")?;
         printer.write_number(self.rlim_max, &FORMATS::DEC)?;
         Ok(())
     }
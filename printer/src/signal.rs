@@ -0,0 +1,356 @@
+use arch::types::{a64, a32};
+
+const SIGNAL: [(u32, &'static str); 31] = [
+(1, "SIGHUP"), (2, "SIGINT"), (3, "SIGQUIT"), (4, "SIGILL"), (5, "SIGTRAP"), (6, "SIGABRT"), (7, "SIGBUS"), (8, "SIGFPE"),
+(9, "SIGKILL"), (10, "SIGUSR1"), (11, "SIGSEGV"), (12, "SIGUSR2"), (13, "SIGPIPE"), (14, "SIGALRM"), (15, "SIGTERM"), (16, "SIGSTKFLT"),
+(17, "SIGCHLD"), (18, "SIGCONT"), (19, "SIGSTOP"), (20, "SIGTSTP"), (21, "SIGTTIN"), (22, "SIGTTOU"), (23, "SIGURG"), (24, "SIGXCPU"),
+(25, "SIGXFSZ"), (26, "SIGVTALRM"), (27, "SIGPROF"), (28, "SIGWINCH"), (29, "SIGIO"), (30, "SIGPWR"), (31, "SIGSYS"),
+];
+
+const SIGRTMIN: u32 = 32;
+const SIGRTMAX: u32 = 64;
+
+/// Write signal number as name, real-time signal is written as SIGRT_n
+pub fn write_signal(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let value = value as u32;
+    if value == 0 {
+        printer.write_number(value, &crate::FORMATS::DEC)
+    } else if (SIGRTMIN..=SIGRTMAX).contains(&value) {
+        printer.write_text(format!("SIGRT_{}", value - SIGRTMIN).as_bytes())
+    } else {
+        printer.write_enum(value, &SIGNAL)
+    }
+}
+
+const SIG_BLOCK: [(u32, &'static str); 3] = [ (0, "SIG_BLOCK"), (1, "SIG_UNBLOCK"), (2, "SIG_SETMASK"), ];
+
+const SA_FLAG: [(u64, &'static str); 10] = [
+(0x00000001, "SA_NOCLDSTOP"), (0x00000002, "SA_NOCLDWAIT"), (0x00000004, "SA_SIGINFO"), (0x00000400, "SA_UNSUPPORTED"),
+(0x00000800, "SA_EXPOSE_TAGBITS"), (0x04000000, "SA_RESTORER"), (0x08000000, "SA_ONSTACK"), (0x10000000, "SA_RESTART"),
+(0x40000000, "SA_NODEFER"), (0x80000000, "SA_RESETHAND"),
+];
+
+/// Limit of sigsetsize to peek, kernel accepts only size of sigset_t
+const SIGSET_MAX_SIZE: usize = 128;
+
+/// Write how argument of rt_sigprocmask
+pub fn write_how(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    printer.write_enum(value as u32, &SIG_BLOCK)
+}
+
+/// Write signal handler, SIG_DFL and SIG_IGN are written as name
+fn write_handler(printer: &crate::Printer, value: u64) -> std::result::Result<(), std::io::Error> {
+    match value {
+        0 => printer.write_text(b"SIG_DFL"),
+        1 => printer.write_text(b"SIG_IGN"),
+        _ => printer.write_number_as_pointer(value),
+    }
+}
+
+/// Write signals contained in sigset words as list of names
+/// # Arguments
+/// * `words` - Words of sigset, lowest signal is bit 0 of first word
+/// * `bits` - Bit width of a word, 64 or 32 for compat_sigset_t
+fn write_sigset_words(printer: &crate::Printer, words: &[u64], bits: u32, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let mut head = true;
+    printer.write(b"[")?;
+    for (index, word) in words.iter().enumerate() {
+        for bit in 0..bits {
+            if word & (1 << bit) == 0 { continue }
+            if !head {
+                printer.write(if printer.is_json() { b", " } else { b" " })?;
+            }
+            head = false;
+            write_signal(printer, index as u64 * bits as u64 + bit as u64 + 1, e)?;
+        }
+    }
+    printer.write(b"]")
+}
+
+/// Write sigset_t in buffer, it is array of unsigned long
+pub fn write_sigset(printer: &crate::Printer, buf: &[u8], _: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    let words: Vec<u64> = if e.is_64() {
+        buf.chunks_exact(8).map(|w| u64::from_ne_bytes(w.try_into().unwrap())).collect()
+    } else {
+        buf.chunks_exact(4).map(|w| u32::from_ne_bytes(w.try_into().unwrap()) as u64).collect()
+    };
+    write_sigset_words(printer, &words, if e.is_64() { 64 } else { 32 }, e)
+}
+
+/// Write sigset_t pointed by addr
+/// # Arguments
+/// * `size` - sigsetsize argument of syscall
+pub fn peek_write_sigset(printer: &crate::Printer, addr: u64, size: usize, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+    if size == 0 || size > SIGSET_MAX_SIZE {
+        return printer.write_number_as_pointer(addr);
+    }
+    printer.peek_write_callback::<u64, _>(addr as types::Ptr, size, write_sigset, pid, e)
+}
+
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct sigaction {
+    sa_handler: a64::Ptr,
+    sa_flags: a64::ULong,
+    sa_restorer: a64::Ptr,
+    sa_mask: [a64::ULong; 1],
+}
+
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct compat_sigaction {
+    sa_handler: a32::Ptr,
+    sa_flags: a32::ULong,
+    sa_restorer: a32::Ptr,
+    sa_mask: [a32::ULong; 2],
+}
+
+macro_rules! sigaction_impl_print {
+    ($type:ty, $bits:expr) => {
+        impl crate::Print for $type {
+            fn print(&self, printer: &crate::Printer, _: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+                printer.write_member("sa_handler")?;
+                write_handler(printer, self.sa_handler as u64)?;
+                printer.write_next_member("sa_mask")?;
+                let words: Vec<u64> = self.sa_mask.iter().map(|w| *w as u64).collect();
+                write_sigset_words(printer, &words, $bits, e)?;
+                printer.write_next_member("sa_flags")?;
+                printer.write_mask_enum(self.sa_flags as u64, &SA_FLAG)?;
+                printer.write_next_member("sa_restorer")?;
+                printer.write_number_as_pointer(self.sa_restorer)
+            }
+        }
+    };
+}
+
+sigaction_impl_print!(sigaction, 64);
+sigaction_impl_print!(compat_sigaction, 32);
+
+const SI_USER: i32 = 0;
+const SI_KERNEL: i32 = 0x80;
+const SI_TIMER: i32 = -2;
+const SI_SIGIO: i32 = -5;
+const CLD_EXITED: i32 = 1;
+const NSIGPOLL: i32 = 6;
+
+const SI_CODE: [(i32, &'static str); 10] = [
+(0, "SI_USER"), (0x80, "SI_KERNEL"), (-1, "SI_QUEUE"), (-2, "SI_TIMER"), (-3, "SI_MESGQ"),
+(-4, "SI_ASYNCIO"), (-5, "SI_SIGIO"), (-6, "SI_TKILL"), (-7, "SI_DETHREAD"), (-60, "SI_ASYNCNL"),
+];
+const ILL_CODE: [(i32, &'static str); 9] = [
+(1, "ILL_ILLOPC"), (2, "ILL_ILLOPN"), (3, "ILL_ILLADR"), (4, "ILL_ILLTRP"), (5, "ILL_PRVOPC"),
+(6, "ILL_PRVREG"), (7, "ILL_COPROC"), (8, "ILL_BADSTK"), (9, "ILL_BADIADDR"),
+];
+const FPE_CODE: [(i32, &'static str); 10] = [
+(1, "FPE_INTDIV"), (2, "FPE_INTOVF"), (3, "FPE_FLTDIV"), (4, "FPE_FLTOVF"), (5, "FPE_FLTUND"),
+(6, "FPE_FLTRES"), (7, "FPE_FLTINV"), (8, "FPE_FLTSUB"), (14, "FPE_FLTUNK"), (15, "FPE_CONDTRAP"),
+];
+const SEGV_CODE: [(i32, &'static str); 10] = [
+(1, "SEGV_MAPERR"), (2, "SEGV_ACCERR"), (3, "SEGV_BNDERR"), (4, "SEGV_PKUERR"), (5, "SEGV_ACCADI"),
+(6, "SEGV_ADIDERR"), (7, "SEGV_ADIPERR"), (8, "SEGV_MTEAERR"), (9, "SEGV_MTESERR"), (10, "SEGV_CPERR"),
+];
+const BUS_CODE: [(i32, &'static str); 5] = [
+(1, "BUS_ADRALN"), (2, "BUS_ADRERR"), (3, "BUS_OBJERR"), (4, "BUS_MCEERR_AR"), (5, "BUS_MCEERR_AO"),
+];
+const TRAP_CODE: [(i32, &'static str); 6] = [
+(1, "TRAP_BRKPT"), (2, "TRAP_TRACE"), (3, "TRAP_BRANCH"), (4, "TRAP_HWBKPT"), (5, "TRAP_UNK"), (6, "TRAP_PERF"),
+];
+const CLD_CODE: [(i32, &'static str); 6] = [
+(1, "CLD_EXITED"), (2, "CLD_KILLED"), (3, "CLD_DUMPED"), (4, "CLD_TRAPPED"), (5, "CLD_STOPPED"), (6, "CLD_CONTINUED"),
+];
+const POLL_CODE: [(i32, &'static str); 6] = [
+(1, "POLL_IN"), (2, "POLL_OUT"), (3, "POLL_MSG"), (4, "POLL_ERR"), (5, "POLL_PRI"), (6, "POLL_HUP"),
+];
+const SYS_CODE: [(i32, &'static str); 2] = [ (1, "SYS_SECCOMP"), (2, "SYS_USER_DISPATCH"), ];
+
+const SIGILL: i32 = 4;
+const SIGTRAP: i32 = 5;
+const SIGBUS: i32 = 7;
+const SIGFPE: i32 = 8;
+const SIGSEGV: i32 = 11;
+const SIGCHLD: i32 = 17;
+const SIGPOLL: i32 = 29;
+const SIGSYS: i32 = 31;
+
+/// Member set of union _sifields in siginfo_t
+#[derive(PartialEq)]
+enum SIFIELDS { KILL, TIMER, RT, CHLD, FAULT, POLL, SYS }
+
+/// Select union member same as siginfo_layout() of kernel
+fn sifields_layout(signo: i32, code: i32) -> SIFIELDS {
+    if code > SI_USER && code < SI_KERNEL {
+        // signal specific codes are up to the limit
+        let (limit, layout) = match signo {
+            SIGILL => (11, SIFIELDS::FAULT),
+            SIGFPE => (15, SIFIELDS::FAULT),
+            SIGSEGV => (10, SIFIELDS::FAULT),
+            SIGBUS => (5, SIFIELDS::FAULT),
+            SIGTRAP => (6, SIFIELDS::FAULT),
+            SIGCHLD => (6, SIFIELDS::CHLD),
+            SIGPOLL => (6, SIFIELDS::POLL),
+            SIGSYS => (2, SIFIELDS::SYS),
+            _ => (0, SIFIELDS::KILL),
+        };
+        if code <= limit {
+            layout
+        } else if code <= NSIGPOLL {
+            SIFIELDS::POLL
+        } else {
+            SIFIELDS::KILL
+        }
+    } else {
+        match code {
+            SI_TIMER => SIFIELDS::TIMER,
+            SI_SIGIO => SIFIELDS::POLL,
+            r if r < 0 => SIFIELDS::RT,
+            _ => SIFIELDS::KILL,
+        }
+    }
+}
+
+fn write_si_code(printer: &crate::Printer, signo: i32, code: i32) -> std::result::Result<(), std::io::Error> {
+    let tbl: &[(i32, &'static str)] = match signo {
+        _ if code <= SI_USER || code >= SI_KERNEL => &SI_CODE,
+        SIGILL => &ILL_CODE,
+        SIGFPE => &FPE_CODE,
+        SIGSEGV => &SEGV_CODE,
+        SIGBUS => &BUS_CODE,
+        SIGTRAP => &TRAP_CODE,
+        SIGCHLD => &CLD_CODE,
+        SIGPOLL => &POLL_CODE,
+        SIGSYS => &SYS_CODE,
+        _ => &SI_CODE,
+    };
+    if printer.try_write_enum(code, tbl)? {
+        Ok(())
+    } else {
+        printer.write_number(code, &crate::FORMATS::DEC)
+    }
+}
+
+/// Read field of union _sifields, out of range is read as zero
+fn sifield<T: Copy + Default>(buf: &[u8], offset: usize) -> T {
+    match buf.get(offset..offset + std::mem::size_of::<T>()) {
+        Some(b) => unsafe { std::ptr::read_unaligned(b.as_ptr() as *const T) },
+        None => T::default(),
+    }
+}
+
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct siginfo {
+    si_signo: a64::SInt,
+    si_errno: a64::SInt,
+    si_code: a64::SInt,
+    __pad: a64::SInt,
+    _sifields: [u8; 112],
+}
+
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct compat_siginfo {
+    si_signo: a32::SInt,
+    si_errno: a32::SInt,
+    si_code: a32::SInt,
+    _sifields: [u8; 116],
+}
+
+macro_rules! siginfo_impl_print {
+    ($type:ty, $ptr:ty, $long:ty) => {
+        impl crate::Print for $type {
+            fn print(&self, printer: &crate::Printer, _: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
+                const P: usize = std::mem::size_of::<$ptr>();
+                let f = &self._sifields;
+                printer.write_member("si_signo")?;
+                write_signal(printer, self.si_signo as u64, e)?;
+                if self.si_errno != 0 {
+                    printer.write_next_member("si_errno")?;
+                    crate::errno::write_errno(printer, self.si_errno as u64, e)?;
+                }
+                printer.write_next_member("si_code")?;
+                write_si_code(printer, self.si_signo, self.si_code)?;
+                let layout = sifields_layout(self.si_signo, self.si_code);
+                match layout {
+                    SIFIELDS::KILL | SIFIELDS::RT | SIFIELDS::CHLD => {
+                        printer.write_next_member("si_pid")?;
+                        printer.write_number(sifield::<i32>(f, 0), &crate::FORMATS::DEC)?;
+                        printer.write_next_member("si_uid")?;
+                        printer.write_number(sifield::<u32>(f, 4), &crate::FORMATS::DEC)?;
+                    },
+                    SIFIELDS::TIMER => {
+                        printer.write_next_member("si_timerid")?;
+                        printer.write_number(sifield::<i32>(f, 0), &crate::FORMATS::DEC)?;
+                        printer.write_next_member("si_overrun")?;
+                        printer.write_number(sifield::<i32>(f, 4), &crate::FORMATS::DEC)?;
+                    },
+                    SIFIELDS::FAULT => {
+                        printer.write_next_member("si_addr")?;
+                        printer.write_number_as_pointer(sifield::<$ptr>(f, 0))?;
+                    },
+                    SIFIELDS::POLL => {
+                        printer.write_next_member("si_band")?;
+                        printer.write_number(sifield::<$long>(f, 0), &crate::FORMATS::DEC)?;
+                        printer.write_next_member("si_fd")?;
+                        printer.write_number(sifield::<i32>(f, P), &crate::FORMATS::DEC)?;
+                    },
+                    SIFIELDS::SYS => {
+                        printer.write_next_member("si_call_addr")?;
+                        printer.write_number_as_pointer(sifield::<$ptr>(f, 0))?;
+                        printer.write_next_member("si_syscall")?;
+                        printer.write_number(sifield::<i32>(f, P), &crate::FORMATS::DEC)?;
+                        printer.write_next_member("si_arch")?;
+                        printer.write_number(sifield::<u32>(f, P + 4), &crate::FORMATS::HEX)?;
+                    },
+                }
+                if layout == SIFIELDS::RT || layout == SIFIELDS::TIMER {
+                    printer.write_next_member("si_int")?;
+                    printer.write_number(sifield::<i32>(f, 8), &crate::FORMATS::DEC)?;
+                    printer.write_next_member("si_ptr")?;
+                    printer.write_number_as_pointer(sifield::<$ptr>(f, 8))?;
+                }
+                if layout == SIFIELDS::CHLD {
+                    // clock_t following int is aligned to size of long
+                    let utime = (12 + P - 1) / P * P;
+                    printer.write_next_member("si_status")?;
+                    if self.si_code == CLD_EXITED {
+                        printer.write_number(sifield::<i32>(f, 8), &crate::FORMATS::DEC)?;
+                    } else {
+                        write_signal(printer, sifield::<i32>(f, 8) as u64, e)?;
+                    }
+                    printer.write_next_member("si_utime")?;
+                    printer.write_number(sifield::<$long>(f, utime), &crate::FORMATS::DEC)?;
+                    printer.write_next_member("si_stime")?;
+                    printer.write_number(sifield::<$long>(f, utime + P), &crate::FORMATS::DEC)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+siginfo_impl_print!(siginfo, a64::Ptr, a64::SLong);
+siginfo_impl_print!(compat_siginfo, a32::Ptr, a32::SLong);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_by_code() {
+        assert!(sifields_layout(10, SI_USER) == SIFIELDS::KILL);
+        assert!(sifields_layout(10, SI_KERNEL) == SIFIELDS::KILL);
+        assert!(sifields_layout(10, -6) == SIFIELDS::RT); // SI_TKILL
+        assert!(sifields_layout(10, -1) == SIFIELDS::RT); // SI_QUEUE
+        assert!(sifields_layout(10, SI_TIMER) == SIFIELDS::TIMER);
+        assert!(sifields_layout(10, SI_SIGIO) == SIFIELDS::POLL);
+    }
+
+    #[test]
+    fn layout_by_signal() {
+        assert!(sifields_layout(SIGSEGV, 1) == SIFIELDS::FAULT);
+        assert!(sifields_layout(SIGCHLD, CLD_EXITED) == SIFIELDS::CHLD);
+        assert!(sifields_layout(SIGPOLL, 1) == SIFIELDS::POLL);
+        assert!(sifields_layout(SIGSYS, 1) == SIFIELDS::SYS);
+        // code above signal specific ones
+        assert!(sifields_layout(SIGSYS, 3) == SIFIELDS::POLL);
+        assert!(sifields_layout(SIGSEGV, 11) == SIFIELDS::KILL);
+        assert!(sifields_layout(10, 1) == SIFIELDS::POLL);
+        assert!(sifields_layout(10, 7) == SIFIELDS::KILL);
+    }
+}
@@ -66,12 +66,28 @@ unsafe fn strlen(base: *const u8) -> usize {
     addr as usize - base as usize
 }
 
+fn write_family(printer: &crate::Printer, member: &str, name: &[u8]) -> std::result::Result<(), std::io::Error> {
+    printer.write_member(member)?;
+    printer.write_text(name)
+}
+
+/// Write network byte order value as hton(N), bare N for JSON
+fn write_hton<T: crate::number::ToString>(printer: &crate::Printer, value: T) -> std::result::Result<(), std::io::Error> {
+    if printer.is_json() {
+        return printer.write_number(value, &FORMATS::DEC);
+    }
+    printer.write(b"hton(")?;
+    printer.write_number(value, &FORMATS::DEC)?;
+    printer.write(b")")
+}
+
 impl crate::Print for sockaddr_un_path {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".sun_family = AF_UNIX")
+        write_family(printer, "sun_family", b"AF_UNIX")
     }
     fn print_flex_tail(&self, printer: &crate::Printer, buf: &[u8], _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write_maybe_ascii(buf)
+        printer.write_next_member("sun_path")?;
+        printer.write_maybe_ascii_str(buf)
     }
     fn flex_tail_size(&self) -> usize {
         let base = self.sun_path.as_ptr();
@@ -87,10 +103,16 @@ impl crate::Print for sockaddr_un_path {
 
 impl crate::Print for sockaddr_un_abstract {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".sun_family = AF_UNIX")
+        write_family(printer, "sun_family", b"AF_UNIX")
     }
     fn print_flex_tail(&self, printer: &crate::Printer, buf: &[u8], _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b", sun_path = \"\\x00")?;
+        printer.write_next_member("sun_path")?;
+        if printer.is_json() {
+            let mut path = vec![0];
+            path.extend_from_slice(buf);
+            return printer.write_str(&path);
+        }
+        printer.write(b"\"\\x00")?;
         printer.write_maybe_ascii(buf)?;
         printer.write(b"\"")
     }
@@ -108,55 +130,42 @@ impl crate::Print for sockaddr_un_abstract {
 
 impl crate::Print for sockaddr_un_unname {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".sun_family = AF_UNIX")?;
-        Ok(())
+        write_family(printer, "sun_family", b"AF_UNIX")
     }
 }
 
 impl crate::Print for sockaddr_in {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".sin_family = AF_INET")?;
-        printer.write(b", .sin_port = hton(")?;
-        printer.write_number(self.sin_port.swap_bytes(), &FORMATS::DEC)?;
-        printer.write(b"), .sin_addr = \"")?;
-        printer.write_number(self.sin_addr[0], &FORMATS::DEC)?;
-        printer.write(b".")?;
-        printer.write_number(self.sin_addr[1], &FORMATS::DEC)?;
-        printer.write(b".")?;
-        printer.write_number(self.sin_addr[2], &FORMATS::DEC)?;
-        printer.write(b".")?;
-        printer.write_number(self.sin_addr[3], &FORMATS::DEC)?;
-        printer.write(b"\"")?;
-        Ok(())
+        write_family(printer, "sin_family", b"AF_INET")?;
+        printer.write_next_member("sin_port")?;
+        write_hton(printer, self.sin_port.swap_bytes())?;
+        printer.write_next_member("sin_addr")?;
+        let a = &self.sin_addr;
+        printer.write_str(format!("{}.{}.{}.{}", a[0], a[1], a[2], a[3]).as_bytes())
     }
 }
 
 impl crate::Print for sockaddr_in6 {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".sin6_family = AF_INET6")?;
-        printer.write(b", .sin6_flowinfo = hton(")?;
-        printer.write_number(self.sin6_flowinfo.swap_bytes(), &FORMATS::DEC)?;
-        printer.write(b", .sin6_port = hton(")?;
-        printer.write_number(self.sin6_port.swap_bytes(), &FORMATS::DEC)?;
-        printer.write(b"), .sin6_addr = \"")?;
-        printer.write_hex(self.sin6_addr[0])?;
-        for addr in self.sin6_addr[1..].iter() {
-            printer.write(b":")?;
-            printer.write_hex(*addr)?;
-        }
-        printer.write(b", .sin6_scope_id = ")?;
-        printer.write_number(self.sin6_scope_id, &FORMATS::DEC)?;
-        printer.write(b"\"")?;
-        Ok(())
+        write_family(printer, "sin6_family", b"AF_INET6")?;
+        printer.write_next_member("sin6_flowinfo")?;
+        write_hton(printer, self.sin6_flowinfo.swap_bytes())?;
+        printer.write_next_member("sin6_port")?;
+        write_hton(printer, self.sin6_port.swap_bytes())?;
+        printer.write_next_member("sin6_addr")?;
+        let addr: Vec<String> = self.sin6_addr.iter().map(|a| format!("{:02x}", a)).collect();
+        printer.write_str(addr.join(":").as_bytes())?;
+        printer.write_next_member("sin6_scope_id")?;
+        printer.write_number(self.sin6_scope_id, &FORMATS::DEC)
     }
 }
 
 impl crate::Print for sockaddr_nl {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".nl_family = AF_NETLINK")?;
-        printer.write(b", .nl_pid = ")?;
+        write_family(printer, "nl_family", b"AF_NETLINK")?;
+        printer.write_next_member("nl_pid")?;
         printer.write_number(self.nl_pid, &FORMATS::DEC)?;
-        printer.write(b", .nl_groups = ")?;
+        printer.write_next_member("nl_groups")?;
         printer.write_number(self.nl_groups, &FORMATS::DEC)?;
         Ok(())
     }
@@ -164,10 +173,10 @@ impl crate::Print for sockaddr_nl {
 
 impl crate::Print for sockaddr_vm {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".svm_family = AF_VSOCK")?;
-        printer.write(b", .svm_port = ")?;
+        write_family(printer, "svm_family", b"AF_VSOCK")?;
+        printer.write_next_member("svm_port")?;
         printer.write_number(self.svm_port, &FORMATS::DEC)?;
-        printer.write(b", .svm_cid = ")?;
+        printer.write_next_member("svm_cid")?;
         printer.write_number(self.svm_cid, &FORMATS::DEC)?;
         Ok(())
     }
@@ -175,10 +184,7 @@ impl crate::Print for sockaddr_vm {
 
 pub fn write_sockaddr(printer: &crate::Printer, buf: &[u8], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     if buf.len() < std::mem::size_of::<SockFamily>() {
-        printer.write(b"{")?;
-        printer.write_as_hex(buf)?;
-        printer.write(b"}")?;
-        Ok(())
+        printer.write_hex_block(buf)
     } else {
         match SockFamily::from_ne_bytes([buf[0], buf[1]]) {
             AF_UNIX => {
@@ -195,9 +201,7 @@ pub fn write_sockaddr(printer: &crate::Printer, buf: &[u8], pid: types::Pid, e:
             AF_NETLINK => { printer.write_struct_from_buf::<sockaddr_nl>(buf, pid, e)?; },
             AF_VSOCK => { printer.write_struct_from_buf::<sockaddr_vm>(buf, pid, e)?; },
             _ => {
-                printer.write(b"{")?;
-                printer.write_as_hex(buf)?;
-                printer.write(b"}")?;
+                printer.write_hex_block(buf)?;
             },
         }
         Ok(())
@@ -51,12 +51,7 @@ macro_rules! offset_of {
 const SEND_FLAG: [(u32, &'static str); 7] = [ (0x800, "MSG_CONFIRM"), (4, "MSG_DONTROUTE"), (0x40, "MSG_DONTWAIT"), (0x80, "MSG_EOR"), (0x8000, "MSG_MORE"), (0x4000, "MSG_NOSIGNAL"), (1, "MSG_OOB"),];
 
 pub fn write_send_flag(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-    if value == 0 {
-        printer.write(b"0")?;
-    } else {
-        printer.write_mask_enum(value as u32, &SEND_FLAG)?;
-    }
-    Ok(())
+    printer.write_mask_enum(value as u32, &SEND_FLAG)
 }
 
 macro_rules! cmsg_align {
@@ -67,18 +62,18 @@ macro_rules! cmsg_align {
 
 macro_rules! cmsg_print {
     ($self:ident, $printer:ident) => {
-        $printer.write(b".cmsg_len = ")?;
+        $printer.write_member("cmsg_len")?;
         $printer.write_number($self.cmsg_len, &FORMATS::DEC)?;
-        $printer.write(b", .cmsg_level = ")?;
+        $printer.write_next_member("cmsg_level")?;
         $printer.write_number($self.cmsg_level, &FORMATS::DEC)?;
-        $printer.write(b", .cmsg_type = ")?;
+        $printer.write_next_member("cmsg_type")?;
         $printer.write_number($self.cmsg_type, &FORMATS::DEC)?;
     };
 }
 
 macro_rules! cmsg_print_tail {
     ($buf:ident, $printer:ident) => {
-        $printer.write(b", .cmsg_data = ")?;
+        $printer.write_next_member("cmsg_data")?;
         $printer.write_as_hex($buf)?;
     };
 }
@@ -118,7 +113,8 @@ macro_rules! cmsg_impl_print {
 
 fn write_cmsghdr_array<T: crate::Print>(printer: &crate::Printer, buf: &[u8], pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     if buf.len() < std::mem::size_of::<T>() {
-        return printer.write(b"{}");
+        printer.write_array_begin()?;
+        return printer.write_array_end();
     }
     printer.write_flex_tail_struct_array_from_buf::<T>(buf, pid, e)?;
     Ok(())
@@ -129,25 +125,25 @@ macro_rules!  msghdr_impl_print {
         cmsg_impl_print!($cmsghdr);
         impl crate::Print for $msghdr {
             fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                printer.write(b".msg_name = ")?;
-                printer.peek_write_maybe_ascii(self.msg_name as types::Ptr, self.msg_namelen as usize, pid, e)?;
-                printer.write(b", .msg_namelen = ")?;
+                printer.write_member("msg_name")?;
+                printer.peek_write_maybe_ascii_str(self.msg_name as types::Ptr, self.msg_namelen as usize, pid, e)?;
+                printer.write_next_member("msg_namelen")?;
                 printer.write_number(self.msg_namelen, &FORMATS::DEC)?;
-                printer.write(b", .msg_iov = ")?;
+                printer.write_next_member("msg_iov")?;
                 if self.msg_iov == 0 || self.msg_iovlen == 0 {
                     printer.write_number(self.msg_iov, &FORMATS::HEX)?;
                 } else {
                     printer.peek_write_struct_array::<$iovec>(self.msg_iov as types::Ptr, self.msg_iovlen as usize, pid, e)?;
                 }
-                printer.write(b", .msg_iovlen = ")?;
+                printer.write_next_member("msg_iovlen")?;
                 printer.write_number(self.msg_iovlen, &FORMATS::DEC)?;
-                printer.write(b", .msg_control = ")?;
+                printer.write_next_member("msg_control")?;
                 if self.msg_control == 0 || (self.msg_controllen as usize) < std::mem::size_of::<$cmsghdr>() {
                     printer.write_number(self.msg_control, &FORMATS::HEX)?;
                 } else {
                     printer.peek_write_callback::<$cmsghdr, _>(self.msg_control as types::Ptr, self.msg_controllen as usize, write_cmsghdr_array::<$cmsghdr>, pid, e)?;
                 }
-                printer.write(b", .msg_controllen = ")?;
+                printer.write_next_member("msg_controllen")?;
                 printer.write_number(self.msg_controllen, &FORMATS::DEC)?;
                 Ok(())
             }
@@ -158,6 +154,24 @@ macro_rules!  msghdr_impl_print {
 msghdr_impl_print!(msghdr, iovec, cmsghdr);
 msghdr_impl_print!(compat_msghdr, compat_iovec, compat_cmsghdr);
 
+macro_rules! msghdr_impl_peek {
+    ($msghdr:ty, $iovec:ty) => {
+        impl $msghdr {
+            /// Peek iovec array of msghdr as pairs of base address and length
+            pub(crate) fn peek_iov(pid: types::Pid, addr: types::Ptr) -> std::result::Result<Vec<(types::Ptr, usize)>, std::io::Error> {
+                let m = peek::peek_data::<$msghdr>(pid, addr)?;
+                if m.msg_iov == 0 {
+                    return Ok(vec![]);
+                }
+                <$iovec>::peek_array(pid, m.msg_iov as types::Ptr, m.msg_iovlen as usize)
+            }
+        }
+    };
+}
+
+msghdr_impl_peek!(msghdr, iovec);
+msghdr_impl_peek!(compat_msghdr, compat_iovec);
+
 const DOMAIN: [(u32, &'static str); 45] = [
 (0, "AF_UNSPEC"), (1, "AF_UNIX"), (2, "AF_INET"), (3, "AF_AX25"), (4, "AF_IPX"),
 (5, "AF_APPLETALK"), (6, "AF_NETROM"), (7, "AF_BRIDGE"), (8, "AF_ATMPVC"), (9, "AF_X25"),
@@ -184,13 +198,15 @@ pub fn write_flag(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery
 
 pub fn write_type(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
+    printer.write_flags_begin()?;
     for (v,n) in SOCK_FLAG.iter() {
         if value == *v {
-            printer.write(n.as_bytes())?;
-            printer.write(b" | ")?;
+            printer.write_text(n.as_bytes())?;
+            printer.write_flags_sep()?;
         }
     }
-    printer.write_enum(value & 0xF, &SOCK_TYPE)
+    printer.write_enum(value & 0xF, &SOCK_TYPE)?;
+    printer.write_flags_end()
 }
 
 const CALL: [(u32, &'static str); 21] = [
@@ -231,29 +247,33 @@ pub fn write_socketcall_arg(printer: &crate::Printer, value: u64, pid: types::Pi
             addr += std::mem::size_of::<a32::ULong>() as types::Ptr;
         }
     }
-    printer.write(b"{")?;
     if e.is_entry() {
+        printer.write_array_begin()?;
         let dummy = peek::SyscallSummery::new_dummy_entry(is64, UNI[call], e.sysnum(), args, 0);
         match printer.conf.get_print_info(dummy.uni_sysnum()) {
             p if p.is_skip() => (),
             p if p.is_undef() => (),
             p  => {
-                printer.write(CALL[call].1.as_bytes())?;
+                printer.write_text(CALL[call].1.as_bytes())?;
                 printer.write(b", ")?;
                 printer.write_args_impl(&p, pid, &dummy)?;
             }
         }
+        printer.write_array_end()
     } else {
+        printer.write(b"{")?;
         let dummy = peek::SyscallSummery::new_dummy_entry(is64, UNI[call], e.sysnum(), args, e.return_value()? as i64);
         match printer.conf.get_print_info_for_ret_args(dummy.uni_sysnum()) {
             p if p.is_skip() => (),
             p if p.is_undef() => (),
             p => {
-                printer.write(CALL[call].1.as_bytes())?;
-                printer.write_ret_args_impl(&p, pid, &dummy)?;
+                if printer.is_json() {
+                    printer.write_key("call")?;
+                }
+                printer.write_text(CALL[call].1.as_bytes())?;
+                printer.write_ret_args_impl(&p, false, pid, &dummy)?;
             },
         }
+        printer.write(b"}")
     }
-    printer.write(b"}")?;
-    Ok(())
 }
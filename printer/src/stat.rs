@@ -12,22 +12,22 @@ pub struct compat_stat {
 
 macro_rules! print_stat {
     ($self:expr, $printer: ident) => {
-        $printer.write(b".st_dev = ")?; $printer.write_number($self.st_dev, &FORMATS::HEX)?;
-        $printer.write(b", .st_ino = ")?; $printer.write_number($self.st_ino, &FORMATS::DEC)?;
-        $printer.write(b", .st_mode = ")?; $printer.write_number($self.st_mode, &FORMATS::OCT)?;
-        $printer.write(b", .st_nlink = ")?; $printer.write_number($self.st_nlink, &FORMATS::DEC)?;
-        $printer.write(b", .st_uid = ")?; $printer.write_number($self.st_uid, &FORMATS::DEC)?;
-        $printer.write(b", .st_gid = ")?; $printer.write_number($self.st_gid, &FORMATS::DEC)?;
-        $printer.write(b", .st_rdev = ")?; $printer.write_number($self.st_rdev, &FORMATS::HEX)?;
-        $printer.write(b", .st_size = ")?; $printer.write_number($self.st_size, &FORMATS::DEC)?;
-        $printer.write(b", .st_blksize = ")?; $printer.write_number($self.st_blksize, &FORMATS::DEC)?;
-        $printer.write(b", .st_blocks = ")?; $printer.write_number($self.st_blocks, &FORMATS::DEC)?;
-        $printer.write(b", .st_atime = ")?; $printer.write_number($self.st_atime, &FORMATS::DEC)?;
-        $printer.write(b", .st_atime_nsec = ")?; $printer.write_number($self.st_atime_nsec, &FORMATS::DEC)?;
-        $printer.write(b", .st_mtime = ")?; $printer.write_number($self.st_mtime, &FORMATS::DEC)?;
-        $printer.write(b", .st_mtime_nsec = ")?; $printer.write_number($self.st_mtime_nsec, &FORMATS::DEC)?;
-        $printer.write(b", .st_ctime = ")?; $printer.write_number($self.st_ctime, &FORMATS::DEC)?;
-        $printer.write(b", .st_ctime_nsec = ")?; $printer.write_number($self.st_ctime_nsec, &FORMATS::DEC)?;
+        $printer.write_member("st_dev")?; $printer.write_number($self.st_dev, &FORMATS::HEX)?;
+        $printer.write_next_member("st_ino")?; $printer.write_number($self.st_ino, &FORMATS::DEC)?;
+        $printer.write_next_member("st_mode")?; $printer.write_number($self.st_mode, &FORMATS::OCT)?;
+        $printer.write_next_member("st_nlink")?; $printer.write_number($self.st_nlink, &FORMATS::DEC)?;
+        $printer.write_next_member("st_uid")?; $printer.write_number($self.st_uid, &FORMATS::DEC)?;
+        $printer.write_next_member("st_gid")?; $printer.write_number($self.st_gid, &FORMATS::DEC)?;
+        $printer.write_next_member("st_rdev")?; $printer.write_number($self.st_rdev, &FORMATS::HEX)?;
+        $printer.write_next_member("st_size")?; $printer.write_number($self.st_size, &FORMATS::DEC)?;
+        $printer.write_next_member("st_blksize")?; $printer.write_number($self.st_blksize, &FORMATS::DEC)?;
+        $printer.write_next_member("st_blocks")?; $printer.write_number($self.st_blocks, &FORMATS::DEC)?;
+        $printer.write_next_member("st_atime")?; $printer.write_number($self.st_atime, &FORMATS::DEC)?;
+        $printer.write_next_member("st_atime_nsec")?; $printer.write_number($self.st_atime_nsec, &FORMATS::DEC)?;
+        $printer.write_next_member("st_mtime")?; $printer.write_number($self.st_mtime, &FORMATS::DEC)?;
+        $printer.write_next_member("st_mtime_nsec")?; $printer.write_number($self.st_mtime_nsec, &FORMATS::DEC)?;
+        $printer.write_next_member("st_ctime")?; $printer.write_number($self.st_ctime, &FORMATS::DEC)?;
+        $printer.write_next_member("st_ctime_nsec")?; $printer.write_number($self.st_ctime_nsec, &FORMATS::DEC)?;
     };
 }
 
@@ -89,44 +89,37 @@ const STATX_ATTR: [(u64, &'static str); 7] = [
 
 impl crate::Print for statx_timestamp {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".tv_sec = ")?; printer.write_number(self.tv_sec, &FORMATS::DEC)?;
-        printer.write(b", .tv_nsec = ")?; printer.write_number(self.tv_nsec, &FORMATS::DEC)
+        printer.write_member("tv_sec")?; printer.write_number(self.tv_sec, &FORMATS::DEC)?;
+        printer.write_next_member("tv_nsec")?; printer.write_number(self.tv_nsec, &FORMATS::DEC)
     }
 }
 
 impl crate::Print for statx {
     fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        if self.stx_mask == 0 { printer.write(b".stx_mask = 0")?; }
-        else { printer.write(b".stx_mask = ")?; printer.write_mask_enum(self.stx_mask, &STATX_MASK)?; }
-        printer.write(b", .stx_blksize = ")?; printer.write_number(self.stx_blksize, &FORMATS::DEC)?;
-        if self.stx_attributes == 0 { printer.write(b", .stx_attributes = 0")?; }
-        else { printer.write(b", .stx_attributes = 0")?; printer.write_mask_enum(self.stx_attributes, &STATX_ATTR)?; }
-        printer.write(b", .stx_nlink = ")?; printer.write_number(self.stx_nlink, &FORMATS::DEC)?;
-        printer.write(b", .stx_uid = ")?; printer.write_number(self.stx_uid, &FORMATS::DEC)?;
-        printer.write(b", .stx_gid = ")?; printer.write_number(self.stx_gid, &FORMATS::DEC)?;
-        printer.write(b", .stx_mode = ")?; printer.write_number(self.stx_mode, &FORMATS::OCT)?;
-        printer.write(b", .stx_ino = ")?; printer.write_number(self.stx_ino, &FORMATS::DEC)?;
-        printer.write(b", .stx_size = ")?; printer.write_number(self.stx_size, &FORMATS::DEC)?;
-        printer.write(b", .stx_blocks = ")?; printer.write_number(self.stx_blocks, &FORMATS::DEC)?;
-        if self.stx_attributes_mask == 0 { printer.write(b", .stx_attributes_mask = 0")?; }
-        else { printer.write(b", .stx_attributes_mask = 0")?; printer.write_mask_enum(self.stx_attributes_mask, &STATX_ATTR)?; }
-        printer.write(b", .stx_atime = {")?; self.stx_atime.print(printer, pid, e)?;
-        printer.write(b"}, .stx_btime = {")?; self.stx_btime.print(printer, pid, e)?;
-        printer.write(b"}, .stx_ctime = {")?; self.stx_ctime.print(printer, pid, e)?;
-        printer.write(b"}, .stx_mtime = {")?; self.stx_mtime.print(printer, pid, e)?;
-        printer.write(b"}, .stx_rdev_major = ")?; printer.write_number(self.stx_rdev_major, &FORMATS::HEX)?;
-        printer.write(b", .stx_rdev_minor = ")?; printer.write_number(self.stx_rdev_minor, &FORMATS::HEX)?;
-        printer.write(b", .stx_dev_major = ")?; printer.write_number(self.stx_dev_major, &FORMATS::HEX)?;
-        printer.write(b", .stx_dev_minor = ")?; printer.write_number(self.stx_dev_minor, &FORMATS::HEX)
+        printer.write_member("stx_mask")?; printer.write_mask_enum(self.stx_mask, &STATX_MASK)?;
+        printer.write_next_member("stx_blksize")?; printer.write_number(self.stx_blksize, &FORMATS::DEC)?;
+        printer.write_next_member("stx_attributes")?; printer.write_mask_enum(self.stx_attributes, &STATX_ATTR)?;
+        printer.write_next_member("stx_nlink")?; printer.write_number(self.stx_nlink, &FORMATS::DEC)?;
+        printer.write_next_member("stx_uid")?; printer.write_number(self.stx_uid, &FORMATS::DEC)?;
+        printer.write_next_member("stx_gid")?; printer.write_number(self.stx_gid, &FORMATS::DEC)?;
+        printer.write_next_member("stx_mode")?; printer.write_number(self.stx_mode, &FORMATS::OCT)?;
+        printer.write_next_member("stx_ino")?; printer.write_number(self.stx_ino, &FORMATS::DEC)?;
+        printer.write_next_member("stx_size")?; printer.write_number(self.stx_size, &FORMATS::DEC)?;
+        printer.write_next_member("stx_blocks")?; printer.write_number(self.stx_blocks, &FORMATS::DEC)?;
+        printer.write_next_member("stx_attributes_mask")?; printer.write_mask_enum(self.stx_attributes_mask, &STATX_ATTR)?;
+        printer.write_next_member("stx_atime")?; printer.write(b"{")?; self.stx_atime.print(printer, pid, e)?; printer.write(b"}")?;
+        printer.write_next_member("stx_btime")?; printer.write(b"{")?; self.stx_btime.print(printer, pid, e)?; printer.write(b"}")?;
+        printer.write_next_member("stx_ctime")?; printer.write(b"{")?; self.stx_ctime.print(printer, pid, e)?; printer.write(b"}")?;
+        printer.write_next_member("stx_mtime")?; printer.write(b"{")?; self.stx_mtime.print(printer, pid, e)?; printer.write(b"}")?;
+        printer.write_next_member("stx_rdev_major")?; printer.write_number(self.stx_rdev_major, &FORMATS::HEX)?;
+        printer.write_next_member("stx_rdev_minor")?; printer.write_number(self.stx_rdev_minor, &FORMATS::HEX)?;
+        printer.write_next_member("stx_dev_major")?; printer.write_number(self.stx_dev_major, &FORMATS::HEX)?;
+        printer.write_next_member("stx_dev_minor")?; printer.write_number(self.stx_dev_minor, &FORMATS::HEX)
     }
 }
 
 pub fn write_newfstatat_flags(printer: &crate::Printer, value: u64, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
     let value = value as u32;
-    if value == 0 {
-        printer.write(b"0")
-    } else {
-        printer.write_mask_enum(value, &arch::types::stat::NEWFSTATAT_FLAG)
-    }
+    printer.write_mask_enum(value, &arch::types::stat::NEWFSTATAT_FLAG)
 }
 
@@ -68,17 +68,17 @@ macro_rules! statfs_impl_print {
     ($type:ty) => {
         impl crate::Print for $type {
             fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                printer.write(b".f_type = ")?; printer.write_number(self.f_type, &FORMATS::HEX)?;
-                printer.write(b", .f_bsize = ")?; printer.write_number(self.f_bsize, &FORMATS::DEC)?;
-                printer.write(b", .f_blocks = ")?; printer.write_number(self.f_blocks, &FORMATS::DEC)?;
-                printer.write(b", .f_bfree = ")?; printer.write_number(self.f_bfree, &FORMATS::DEC)?;
-                printer.write(b", .f_bavail = ")?; printer.write_number(self.f_bavail, &FORMATS::DEC)?;
-                printer.write(b", .f_files = ")?; printer.write_number(self.f_files, &FORMATS::DEC)?;
-                printer.write(b", .f_ffree = ")?; printer.write_number(self.f_ffree, &FORMATS::DEC)?;
-                printer.write(b", .f_sid = ")?; printer.write_struct_array(&self.f_fsid, pid, e)?;
-                printer.write(b", .f_namelen = ")?; printer.write_number(self.f_namelen, &FORMATS::DEC)?;
-                printer.write(b", .f_frsize = ")?; printer.write_number(self.f_frsize, &FORMATS::DEC)?;
-                printer.write(b", .f_flags = ")?; printer.write_number(self.f_flags, &FORMATS::HEX)?;
+                printer.write_member("f_type")?; printer.write_number(self.f_type, &FORMATS::HEX)?;
+                printer.write_next_member("f_bsize")?; printer.write_number(self.f_bsize, &FORMATS::DEC)?;
+                printer.write_next_member("f_blocks")?; printer.write_number(self.f_blocks, &FORMATS::DEC)?;
+                printer.write_next_member("f_bfree")?; printer.write_number(self.f_bfree, &FORMATS::DEC)?;
+                printer.write_next_member("f_bavail")?; printer.write_number(self.f_bavail, &FORMATS::DEC)?;
+                printer.write_next_member("f_files")?; printer.write_number(self.f_files, &FORMATS::DEC)?;
+                printer.write_next_member("f_ffree")?; printer.write_number(self.f_ffree, &FORMATS::DEC)?;
+                printer.write_next_member("f_sid")?; printer.write_struct_array(&self.f_fsid, pid, e)?;
+                printer.write_next_member("f_namelen")?; printer.write_number(self.f_namelen, &FORMATS::DEC)?;
+                printer.write_next_member("f_frsize")?; printer.write_number(self.f_frsize, &FORMATS::DEC)?;
+                printer.write_next_member("f_flags")?; printer.write_number(self.f_flags, &FORMATS::HEX)?;
                 Ok(())
             }
         }
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+struct Stat {
+    nr: arch::sys_uni::NR,
+    calls: u64,
+    errors: Vec<(u32, u64)>,
+    total: Duration,
+    max: Duration,
+}
+
+impl Stat {
+    fn new(nr: arch::sys_uni::NR) -> Self {
+        Stat{ nr, calls: 0, errors: vec![], total: Duration::ZERO, max: Duration::ZERO }
+    }
+
+    fn error_count(&self) -> u64 {
+        self.errors.iter().map(|(_, n)| n).sum()
+    }
+
+    fn add(&mut self, e: &peek::SyscallSummery) {
+        self.calls += 1;
+        let d = e.duration().unwrap_or(Duration::ZERO);
+        self.total += d;
+        self.max = std::cmp::max(self.max, d);
+        if let Err(r) = e.return_value() {
+            let errno = r.raw_os_error().unwrap_or(0).unsigned_abs();
+            match self.errors.iter_mut().find(|(n, _)| *n == errno) {
+                Some((_, count)) => *count += 1,
+                None => self.errors.push((errno, 1)),
+            }
+        }
+    }
+}
+
+/// Per syscall statistics for summary output
+pub struct Summary {
+    stats: std::cell::RefCell<Vec<Stat>>,
+}
+
+impl Summary {
+    pub fn new() -> Self {
+        Summary{ stats: std::cell::RefCell::new(vec![]) }
+    }
+
+    /// Add syscall-exited summery to statistics
+    pub fn add(&self, e: &peek::SyscallSummery) {
+        let mut stats = self.stats.borrow_mut();
+        let nr = e.uni_sysnum();
+        match stats.iter_mut().find(|s| s.nr == nr) {
+            Some(s) => s.add(e),
+            None => {
+                let mut s = Stat::new(nr);
+                s.add(e);
+                stats.push(s);
+            },
+        }
+    }
+
+    fn write_line(printer: &crate::Printer, name: &str, calls: u64, errors: u64, total: Duration, max: Duration) -> std::result::Result<(), std::io::Error> {
+        let avg = if calls == 0 { 0 } else { total.as_micros() / calls as u128 };
+        printer.write(format!("{:<24} {:>9} {:>9} {:>6}.{:06} {:>11} {:>11}", name, calls, errors, total.as_secs(), total.subsec_micros(), avg, max.as_micros()).as_bytes())?;
+        printer.flush_line()
+    }
+
+    /// Write statistics table, sorted by total time
+    pub fn write(&self, printer: &crate::Printer) -> std::result::Result<(), std::io::Error> {
+        let mut stats = self.stats.borrow_mut();
+        stats.sort_by(|a, b| b.total.cmp(&a.total).then(b.calls.cmp(&a.calls)));
+        printer.write(format!("{:<24} {:>9} {:>9} {:>13} {:>11} {:>11}", "syscall", "calls", "errors", "total(s)", "avg(us)", "max(us)").as_bytes())?;
+        printer.flush_line()?;
+        printer.write(&[b'-'; 82])?;
+        printer.flush_line()?;
+        for s in stats.iter() {
+            Self::write_line(printer, arch::sys_uni::to_str(s.nr), s.calls, s.error_count(), s.total, s.max)?;
+            for (errno, count) in s.errors.iter() {
+                printer.write(format!("    {:<20} {:>9} {:>9}", crate::errno::errno_name(*errno).unwrap_or("?"), "", count).as_bytes())?;
+                printer.flush_line()?;
+            }
+        }
+        printer.write(&[b'-'; 82])?;
+        printer.flush_line()?;
+        let calls = stats.iter().map(|s| s.calls).sum();
+        let errors = stats.iter().map(|s| s.error_count()).sum();
+        let total = stats.iter().map(|s| s.total).sum();
+        let max = stats.iter().map(|s| s.max).max().unwrap_or(Duration::ZERO);
+        Self::write_line(printer, "total", calls, errors, total, max)
+    }
+}
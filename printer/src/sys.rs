@@ -39,18 +39,18 @@ macro_rules! sysinfo_impl_print {
     ($type:ty) => {
         impl crate::Print for $type {
             fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                printer.write(b".uptime = ")?; printer.write_number(self.uptime, &FORMATS::DEC)?;
-                printer.write(b", .loads = ")?; printer.write_number_array(&self.loads, &FORMATS::DEC)?;
-                printer.write(b", .totalram = ")?; printer.write_number(self.totalram, &FORMATS::DEC)?;
-                printer.write(b", .freeram = ")?; printer.write_number(self.freeram, &FORMATS::DEC)?;
-                printer.write(b", .sharedram = ")?; printer.write_number(self.sharedram, &FORMATS::DEC)?;
-                printer.write(b", .bufferram = ")?; printer.write_number(self.bufferram, &FORMATS::DEC)?;
-                printer.write(b", .totalswap = ")?; printer.write_number(self.totalswap, &FORMATS::DEC)?;
-                printer.write(b", .freeswap = ")?; printer.write_number(self.freeswap, &FORMATS::DEC)?;
-                printer.write(b", .procs = ")?; printer.write_number(self.procs, &FORMATS::DEC)?;
-                printer.write(b", .totalhigh = ")?; printer.write_number(self.totalhigh, &FORMATS::DEC)?;
-                printer.write(b", .freehigh = ")?; printer.write_number(self.freehigh, &FORMATS::DEC)?;
-                printer.write(b", .mem_unit = ")?; printer.write_number(self.mem_unit, &FORMATS::DEC)?;
+                printer.write_member("uptime")?; printer.write_number(self.uptime, &FORMATS::DEC)?;
+                printer.write_next_member("loads")?; printer.write_number_array(&self.loads, &FORMATS::DEC)?;
+                printer.write_next_member("totalram")?; printer.write_number(self.totalram, &FORMATS::DEC)?;
+                printer.write_next_member("freeram")?; printer.write_number(self.freeram, &FORMATS::DEC)?;
+                printer.write_next_member("sharedram")?; printer.write_number(self.sharedram, &FORMATS::DEC)?;
+                printer.write_next_member("bufferram")?; printer.write_number(self.bufferram, &FORMATS::DEC)?;
+                printer.write_next_member("totalswap")?; printer.write_number(self.totalswap, &FORMATS::DEC)?;
+                printer.write_next_member("freeswap")?; printer.write_number(self.freeswap, &FORMATS::DEC)?;
+                printer.write_next_member("procs")?; printer.write_number(self.procs, &FORMATS::DEC)?;
+                printer.write_next_member("totalhigh")?; printer.write_number(self.totalhigh, &FORMATS::DEC)?;
+                printer.write_next_member("freehigh")?; printer.write_number(self.freehigh, &FORMATS::DEC)?;
+                printer.write_next_member("mem_unit")?; printer.write_number(self.mem_unit, &FORMATS::DEC)?;
                 Ok(())
             }
         }
@@ -1,5 +1,79 @@
 use crate::FORMATS;
 use arch::types::{a64, a32};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod c {
+    extern "C" {
+        pub fn localtime_r(timep: *const types::SLong, result: *mut tm) -> *mut tm;
+    }
+
+    #[repr(C)]#[allow(non_camel_case_types)]
+    pub struct tm {
+        pub tm_sec: types::SInt,
+        pub tm_min: types::SInt,
+        pub tm_hour: types::SInt,
+        pub tm_mday: types::SInt,
+        pub tm_mon: types::SInt,
+        pub tm_year: types::SInt,
+        pub tm_wday: types::SInt,
+        pub tm_yday: types::SInt,
+        pub tm_isdst: types::SInt,
+        pub tm_gmtoff: types::SLong,
+        pub tm_zone: *const types::SChar,
+    }
+}
+
+/// Base time for log timestamp, pair of wall clock and monotonic clock at trace start
+pub struct Clock {
+    wall: SystemTime,
+    mono: Instant,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock{ wall: SystemTime::now(), mono: Instant::now() }
+    }
+
+    /// Create with trace start of recorded trace
+    pub fn at(wall: SystemTime, mono: Instant) -> Self {
+        Clock{ wall, mono }
+    }
+
+    /// Get pair of wall clock and monotonic clock at trace start
+    pub fn start(&self) -> (SystemTime, Instant) {
+        (self.wall, self.mono)
+    }
+}
+
+/// Write local wall clock time as HH:MM:SS.uuuuuu
+pub fn write_wall_clock(printer: &crate::Printer, clock: &Clock, t: Instant) -> std::result::Result<(), std::io::Error> {
+    let wall = clock.wall + t.saturating_duration_since(clock.mono);
+    let since_epoch = wall.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+    let sec = since_epoch.as_secs() as types::SLong;
+    let mut tm = std::mem::MaybeUninit::<c::tm>::uninit();
+    if unsafe { c::localtime_r(&sec, tm.as_mut_ptr()) }.is_null() {
+        return printer.write_quoted_if_json(format!("{}.{:06}", sec, since_epoch.subsec_micros()).as_bytes());
+    }
+    let tm = unsafe { tm.assume_init() };
+    printer.write_quoted_if_json(format!("{:02}:{:02}:{:02}.{:06}", tm.tm_hour, tm.tm_min, tm.tm_sec, since_epoch.subsec_micros()).as_bytes())
+}
+
+/// Write elapsed time from trace start as seconds
+pub fn write_relative(printer: &crate::Printer, clock: &Clock, t: Instant) -> std::result::Result<(), std::io::Error> {
+    let d = t.saturating_duration_since(clock.mono);
+    if printer.is_json() {
+        return printer.write(format!("{}.{:06}", d.as_secs(), d.subsec_micros()).as_bytes());
+    }
+    printer.write(format!("{:4}.{:06}", d.as_secs(), d.subsec_micros()).as_bytes())
+}
+
+/// Write syscall duration as <seconds>, bare seconds for JSON
+pub fn write_duration(printer: &crate::Printer, d: Duration) -> std::result::Result<(), std::io::Error> {
+    if printer.is_json() {
+        return printer.write(format!("{}.{:06}", d.as_secs(), d.subsec_micros()).as_bytes());
+    }
+    printer.write(format!("<{}.{:06}>", d.as_secs(), d.subsec_micros()).as_bytes())
+}
 
 #[repr(C)]#[allow(non_camel_case_types)]
 pub struct kernel_timespec {
@@ -7,6 +81,12 @@ pub struct kernel_timespec {
     tv_nsec: types::SLLong,
 }
 
+#[repr(C)]#[allow(non_camel_case_types)]
+pub struct compat_timespec {
+    tv_sec: a32::SLong,
+    tv_nsec: a32::SLong,
+}
+
 #[repr(C)]#[allow(non_camel_case_types)]
 pub struct timeval {
     tv_sec: a64::SLong,
@@ -57,47 +137,48 @@ macro_rules! timeval_impl_print {
     ($type:ty) => {
         impl crate::Print for $type {
             fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                printer.write(b".tv_sec = ")?; printer.write_number(self.tv_sec, &FORMATS::DEC)?;
-                printer.write(b", .tv_nsec = ")?; printer.write_number(self.tv_nsec, &FORMATS::DEC)
+                printer.write_member("tv_sec")?; printer.write_number(self.tv_sec, &FORMATS::DEC)?;
+                printer.write_next_member("tv_nsec")?; printer.write_number(self.tv_nsec, &FORMATS::DEC)
             }
         }
     };
 }
 
 timeval_impl_print!(kernel_timespec);
+timeval_impl_print!(compat_timespec);
 
 timeval_impl_print!(timeval);
 timeval_impl_print!(compat_timeval);
 
 impl crate::Print for timezone {
     fn print(&self, printer: &crate::Printer, _: types::Pid, _: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".tz_minuteswest = ")?; printer.write_number(self.tz_minuteswest, &FORMATS::DEC)?;
-        printer.write(b", .tz_dsttime = ")?; printer.write_number(self.tz_dsttime, &FORMATS::DEC)
+        printer.write_member("tz_minuteswest")?; printer.write_number(self.tz_minuteswest, &FORMATS::DEC)?;
+        printer.write_next_member("tz_dsttime")?; printer.write_number(self.tz_dsttime, &FORMATS::DEC)
     }
 }
 
 impl crate::Print for timex {
     fn print(&self, printer: &crate::Printer, pid: types::Pid, e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        printer.write(b".modes = ")?; printer.write_number(self.modes, &FORMATS::DEC)?;
-        printer.write(b", .offset = ")?; printer.write_number(self.offset, &FORMATS::DEC)?;
-        printer.write(b", .freq = ")?; printer.write_number(self.freq, &FORMATS::DEC)?;
-        printer.write(b", .maxerror = ")?; printer.write_number(self.maxerror, &FORMATS::DEC)?;
-        printer.write(b", .esterror = ")?; printer.write_number(self.esterror, &FORMATS::DEC)?;
-        printer.write(b", .status = ")?; printer.write_number(self.status, &FORMATS::DEC)?;
-        printer.write(b", .constant = ")?; printer.write_number(self.constant, &FORMATS::DEC)?;
-        printer.write(b", .precision = ")?; printer.write_number(self.precision, &FORMATS::DEC)?;
-        printer.write(b", .tolerance = ")?; printer.write_number(self.tolerance, &FORMATS::DEC)?;
-        printer.write(b", .time = {")?; self.time.print(printer, pid, e)?;
-        printer.write(b"}, .tick = ")?; printer.write_number(self.tick, &FORMATS::DEC)?;
-        printer.write(b", .ppsfreq = ")?; printer.write_number(self.ppsfreq, &FORMATS::DEC)?;
-        printer.write(b", .jitter = ")?; printer.write_number(self.jitter, &FORMATS::DEC)?;
-        printer.write(b", .shift = ")?; printer.write_number(self.shift, &FORMATS::DEC)?;
-        printer.write(b", .stabil = ")?; printer.write_number(self.stabil, &FORMATS::DEC)?;
-        printer.write(b", .jitcnt = ")?; printer.write_number(self.jitcnt, &FORMATS::DEC)?;
-        printer.write(b", .calcnt = ")?; printer.write_number(self.calcnt, &FORMATS::DEC)?;
-        printer.write(b", .errcnt = ")?; printer.write_number(self.errcnt, &FORMATS::DEC)?;
-        printer.write(b", .stbcnt = ")?; printer.write_number(self.stbcnt, &FORMATS::DEC)?;
-        printer.write(b", .tai = ")?; printer.write_number(self.tai, &FORMATS::DEC)?;
+        printer.write_member("modes")?; printer.write_number(self.modes, &FORMATS::DEC)?;
+        printer.write_next_member("offset")?; printer.write_number(self.offset, &FORMATS::DEC)?;
+        printer.write_next_member("freq")?; printer.write_number(self.freq, &FORMATS::DEC)?;
+        printer.write_next_member("maxerror")?; printer.write_number(self.maxerror, &FORMATS::DEC)?;
+        printer.write_next_member("esterror")?; printer.write_number(self.esterror, &FORMATS::DEC)?;
+        printer.write_next_member("status")?; printer.write_number(self.status, &FORMATS::DEC)?;
+        printer.write_next_member("constant")?; printer.write_number(self.constant, &FORMATS::DEC)?;
+        printer.write_next_member("precision")?; printer.write_number(self.precision, &FORMATS::DEC)?;
+        printer.write_next_member("tolerance")?; printer.write_number(self.tolerance, &FORMATS::DEC)?;
+        printer.write_next_member("time")?; printer.write(b"{")?; self.time.print(printer, pid, e)?; printer.write(b"}")?;
+        printer.write_next_member("tick")?; printer.write_number(self.tick, &FORMATS::DEC)?;
+        printer.write_next_member("ppsfreq")?; printer.write_number(self.ppsfreq, &FORMATS::DEC)?;
+        printer.write_next_member("jitter")?; printer.write_number(self.jitter, &FORMATS::DEC)?;
+        printer.write_next_member("shift")?; printer.write_number(self.shift, &FORMATS::DEC)?;
+        printer.write_next_member("stabil")?; printer.write_number(self.stabil, &FORMATS::DEC)?;
+        printer.write_next_member("jitcnt")?; printer.write_number(self.jitcnt, &FORMATS::DEC)?;
+        printer.write_next_member("calcnt")?; printer.write_number(self.calcnt, &FORMATS::DEC)?;
+        printer.write_next_member("errcnt")?; printer.write_number(self.errcnt, &FORMATS::DEC)?;
+        printer.write_next_member("stbcnt")?; printer.write_number(self.stbcnt, &FORMATS::DEC)?;
+        printer.write_next_member("tai")?; printer.write_number(self.tai, &FORMATS::DEC)?;
         Ok(())
     }
 }
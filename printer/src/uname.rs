@@ -28,24 +28,24 @@ pub struct new_utsname {
 }
 
 macro_rules! impl_print_null_sentinel_str {
-    ($self:ident, $printer:ident, $member:ident, $prefix:expr) => {
-        $printer.write($prefix)?;
+    ($self:ident, $printer:ident, $member:ident, $write_member:ident) => {
+        $printer.$write_member(stringify!($member))?;
         if let Some(pos) = $self.$member.iter().position(|x| *x == 0) {
-            $printer.write(&$self.$member[0..pos])?;
+            $printer.write_text(&$self.$member[0..pos])?;
         } else {
-            $printer.write(b"\"\"")?;
+            $printer.write_str(b"")?;
         }
     };
 }
 
 impl crate::Print for new_utsname {
     fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-        impl_print_null_sentinel_str!(self, printer, sysname, b".sysname = ");
-        impl_print_null_sentinel_str!(self, printer, nodename, b", .nodename = ");
-        impl_print_null_sentinel_str!(self, printer, release, b", .release = ");
-        impl_print_null_sentinel_str!(self, printer, version, b", .version = ");
-        impl_print_null_sentinel_str!(self, printer, machine, b", .machine = ");
-        impl_print_null_sentinel_str!(self, printer, domainname, b", .domainname = ");
+        impl_print_null_sentinel_str!(self, printer, sysname, write_member);
+        impl_print_null_sentinel_str!(self, printer, nodename, write_next_member);
+        impl_print_null_sentinel_str!(self, printer, release, write_next_member);
+        impl_print_null_sentinel_str!(self, printer, version, write_next_member);
+        impl_print_null_sentinel_str!(self, printer, machine, write_next_member);
+        impl_print_null_sentinel_str!(self, printer, domainname, write_next_member);
         Ok(())
     }
 }
@@ -54,11 +54,11 @@ macro_rules! old_utsname_impl_print {
     ($type:ty) => {
         impl crate::Print for $type {
             fn print(&self, printer: &crate::Printer, _pid: types::Pid, _e: &peek::SyscallSummery) -> std::result::Result<(), std::io::Error> {
-                impl_print_null_sentinel_str!(self, printer, sysname, b".sysname = ");
-                impl_print_null_sentinel_str!(self, printer, nodename, b", .nodename = ");
-                impl_print_null_sentinel_str!(self, printer, release, b", .release = ");
-                impl_print_null_sentinel_str!(self, printer, version, b", .version = ");
-                impl_print_null_sentinel_str!(self, printer, machine, b", .machine = ");
+                impl_print_null_sentinel_str!(self, printer, sysname, write_member);
+                impl_print_null_sentinel_str!(self, printer, nodename, write_next_member);
+                impl_print_null_sentinel_str!(self, printer, release, write_next_member);
+                impl_print_null_sentinel_str!(self, printer, version, write_next_member);
+                impl_print_null_sentinel_str!(self, printer, machine, write_next_member);
                 Ok(())
             }
         }
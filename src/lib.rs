@@ -14,7 +14,19 @@ fn print_usage(bin: &str) -> ! {
 Usage:
     {} [Option] [CMD ARGs...]
 Option:
-    -p: tracee process ids, separated comma.
+    -p: tracee process ids, separated comma. all threads of the processes are traced.
+        @name attaches processes whose comm or command name is name.
+    --cgroup: attach all processes in cgroup directory, their new children are traced by fork.
+    --all-in-pidns: attach all processes in pid namespace of specified pid.
+    --tid: tracee thread ids, separated comma. other threads in same process are not traced.
+    -o: output file path. default stdout.
+    -u: run CMD as specified user name or uid, with its groups.
+    --env: set environment variable of CMD by VAR=val, or unset by VAR. can be specified multiple times.
+    --cwd: run CMD in specified directory.
+    --output-separately: output to file <path>.<pid> per process specified by -o, threads share file of its process.
+    --record: write syscalls and tracee memory read to print them to binary file, in addition to print.
+    --replay: print syscalls recorded by --record from file instead of tracing, with print options e.g. -e, -s, -S, -t, -y and --json.
+              tracee memory not read at record is printed as address, so record without filter options to keep all.
     -e: print only specified name's syscalls, separated comma. default all print.
     --ee: print only inclusive named syscalls, separated comma. default all print.
     -E: not print spefified name's syscalls, separated comma.
@@ -23,12 +35,41 @@ Option:
     --ss: change print format to simple for inclusive named syscalls, separated comma.
     -S: change print format to nopeek tracee memory for spefified name's syscalls, separated comma.
     --SS: change print format to nopeek tracee memory for inclusive named syscalls, separated comma.
+    -t: print wall clock time with microseconds at head of each line.
+    -r: print relative time from trace start at head of each line.
+    -T: print time spent in syscall at tail of syscall-exit line.
+    -c: print only summary of syscall count, error and time when trace finished.
+    -C: print summary of syscall count, error and time when trace finished in addition to each syscall.
+    --merge: print syscall entry and exit as one line, split into unfinished and resumed lines only if other thread's syscall interleaves.
+    --json: print each completed syscall as JSON object per line.
+    -y: print paths associated with fd arguments and return values.
+    -P: print only syscalls touching specified path or files under it, or fds opened from it. can be specified multiple times.
+    --failed-only: print only failed syscalls. syscall-entry line is printed at syscall-exit.
+    --successful-only: print only succeeded syscalls. syscall-entry line is printed at syscall-exit.
+    --errno: print only syscalls failed with specified errno names or numbers, separated comma.
+    --inject: inject to syscalls by SET:OPTION[:OPTION...], SET is syscall names separated comma, OPTION is
+              error=ERRNO to fail, retval=N to succeed with N without executing syscall,
+              delay_enter=T and delay_exit=T to keep tracee stopped at syscall-entry and syscall-exit, T is number with s, ms, us or ns,
+              when=EXPR to select calls, EXPR is first[..last][+[step]] counted per thread,
+              e.g. openat:error=ENOSPC:when=3+, write:delay_enter=10ms,delay_exit=5ms. can be specified multiple times.
+    --redirect-path: replace path argument by FROM=TO, e.g. /etc/resolv.conf=/tmp/test-resolv.conf. can be specified multiple times.
+    --dump-io: write complete data of read, write, send, recv and their variants on fd number or fd path matched to glob, e.g. 3, socket:*,
+               to dump/PID.FD.in and dump/PID.FD.out, syscall line prints the file and offset instead of the buffer. can be specified multiple times.
+    syscall names of -e, -E, -s and -S accept class name, %file, %desc, %network, %process, %memory, %signal and %ipc,
+    regex enclosed by slash e.g. /^(p)?read/, and ! prefix to select syscalls except the rest e.g. !write.
+    unknown syscall name is error.
+    --no-follow: not trace children of tracee. specify before -p, --tid, --cgroup or --all-in-pidns.
+    --follow-threads: trace threads of tracee but not child processes. specify before -p, --tid, --cgroup or --all-in-pidns.
+    --follow-exec: print only processes executed binary matched to the glob pattern and its descendants, other processes are traced silently.
+                   pattern is matched to full path if contains /, otherwise base name. can be specified multiple times.
+    --seccomp-bpf: stop tracee only on printed syscalls by seccomp filter. ignored if processes are attached, --no-follow, --follow-threads or --follow-exec specified.
 "#,
         bin
     );
     std::process::exit(1);
 }
 
+#[allow(clippy::if_same_then_else)]
 fn parse_opt_cb<T>(tracer: &mut Tracer, value: &str, args: &mut Args, expect: &str, cb: T) -> bool
 where
     T: Fn(&mut Tracer, &str),
@@ -52,12 +93,32 @@ where
     T: Fn(&mut Tracer, &str),
 {
     parse_opt_cb(tracer, value, args, expect, |t: &mut Tracer, v: &str| {
-        v.split(',').for_each(|x| {
+        split_comma(v).into_iter().for_each(|x| {
             cb(t, x);
         });
     })
 }
 
+/// Split by comma, except comma in regex enclosed by slash e.g. /^[a-z]{3,4}$/
+fn split_comma(v: &str) -> Vec<&str> {
+    let mut r = vec![];
+    let mut begin = 0;
+    let mut in_regex = false;
+    for (i, c) in v.char_indices() {
+        match c {
+            '/' if i == begin || (i == begin + 1 && v[begin..].starts_with('!')) => in_regex = true,
+            '/' => in_regex = false,
+            ',' if !in_regex => {
+                r.push(&v[begin..i]);
+                begin = i + 1;
+            },
+            _ => {},
+        }
+    }
+    r.push(&v[begin..]);
+    r
+}
+
 fn set_print_skip_for_default_once(tracer: &mut Tracer) {
     static CALLED: AtomicBool = AtomicBool::new(false);
     if !CALLED.load(Ordering::Acquire) {
@@ -66,9 +127,16 @@ fn set_print_skip_for_default_once(tracer: &mut Tracer) {
     }
 }
 
+fn exit_by_error(r: std::result::Result<(), std::io::Error>) {
+    if let Err(e) = r {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
 fn set_print_not_skip_named_syscall(tracer: &mut Tracer, value: &str) {
     set_print_skip_for_default_once(tracer);
-    tracer.set_print_not_skip_by_name(value);
+    exit_by_error(tracer.set_print_not_skip_by_name(value));
 }
 
 fn set_print_not_skip_included_name_syscall(tracer: &mut Tracer, value: &str) {
@@ -77,7 +145,7 @@ fn set_print_not_skip_included_name_syscall(tracer: &mut Tracer, value: &str) {
 }
 
 fn set_print_skip_named_syscall(tracer: &mut Tracer, value: &str) {
-    tracer.set_print_skip_by_name(value);
+    exit_by_error(tracer.set_print_skip_by_name(value));
 }
 
 fn set_print_skip_included_name_syscall(tracer: &mut Tracer, value: &str) {
@@ -85,7 +153,7 @@ fn set_print_skip_included_name_syscall(tracer: &mut Tracer, value: &str) {
 }
 
 fn set_print_simple_named_syscall(tracer: &mut Tracer, value: &str) {
-    tracer.set_print_simple_by_name(value);
+    exit_by_error(tracer.set_print_simple_by_name(value));
 }
 
 fn set_print_simple_included_name_syscall(tracer: &mut Tracer, value: &str) {
@@ -93,7 +161,7 @@ fn set_print_simple_included_name_syscall(tracer: &mut Tracer, value: &str) {
 }
 
 fn set_print_nopeek_named_syscall(tracer: &mut Tracer, value: &str) {
-    tracer.set_print_nopeek_by_name(value);
+    exit_by_error(tracer.set_print_nopeek_by_name(value));
 }
 
 fn set_print_nopeek_included_name_syscall(tracer: &mut Tracer, value: &str) {
@@ -101,13 +169,81 @@ fn set_print_nopeek_included_name_syscall(tracer: &mut Tracer, value: &str) {
 }
 
 fn collect_pid_for_attach(tracer: &mut Tracer, value: &str) {
-    tracer.attach_running_process(value.parse::<types::Pid>().unwrap()).unwrap_or_else(|_|{});
+    if let Some(name) = value.strip_prefix('@') {
+        exit_by_error(tracer.attach_by_name(name));
+        return;
+    }
+    exit_by_error(parse_pid(value).and_then(|pid| tracer.attach_running_process(pid)));
+}
+
+fn attach_cgroup(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.attach_cgroup(value));
+}
+
+fn parse_pid(value: &str) -> std::result::Result<types::Pid, std::io::Error> {
+    value.parse::<types::Pid>().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{}: {}", value, e)))
+}
+
+fn attach_pidns(tracer: &mut Tracer, value: &str) {
+    exit_by_error(parse_pid(value).and_then(|pid| tracer.attach_pidns(pid)));
+}
+
+fn collect_tid_for_attach(tracer: &mut Tracer, value: &str) {
+    exit_by_error(parse_pid(value).and_then(|tid| tracer.attach_running_thread(tid)));
+}
+
+fn add_errno_filter(tracer: &mut Tracer, value: &str) {
+    if !tracer.add_errno_filter(value) {
+        eprintln!("unknown errno: {}", value);
+        std::process::exit(1);
+    }
+}
+
+fn add_follow_exec(tracer: &mut Tracer, value: &str) {
+    tracer.add_follow_exec(value);
+}
+
+fn add_path_filter(tracer: &mut Tracer, value: &str) {
+    tracer.add_path_filter(value);
+}
+
+fn add_inject(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.add_inject(value));
+}
+
+fn add_redirect_path(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.add_redirect_path(value));
+}
+
+fn add_dump_io(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.add_dump_io(value));
+}
+
+fn set_exec_user(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.set_exec_user(value));
+}
+
+fn set_exec_env(tracer: &mut Tracer, value: &str) {
+    tracer.set_exec_env(value);
+}
+
+fn set_exec_cwd(tracer: &mut Tracer, value: &str) {
+    tracer.set_exec_cwd(value);
 }
 
 fn set_output(tracer: &mut Tracer, value: &str) {
-    tracer.set_output(value);
+    exit_by_error(tracer.set_output(value));
+}
+
+fn set_record(tracer: &mut Tracer, value: &str) {
+    exit_by_error(tracer.set_record(value));
+}
+
+fn set_replay(tracer: &mut Tracer, value: &str) {
+    tracer.set_replay(value);
 }
 
+#[allow(clippy::if_same_then_else)]
 fn parse_opt(tracer: &mut Tracer) {
     let mut args = std::env::args();
     let bin = args.next().unwrap();
@@ -115,10 +251,80 @@ fn parse_opt(tracer: &mut Tracer) {
         let head = args.next().unwrap();
         if head == "-h" {
             print_usage(&bin);
+        } else if head == "-t" {
+            tracer.set_timestamp(printer::TIMESTAMP::WALL);
+            continue;
+        } else if head == "-r" {
+            tracer.set_timestamp(printer::TIMESTAMP::RELATIVE);
+            continue;
+        } else if head == "-T" {
+            tracer.set_duration();
+            continue;
+        } else if head == "-c" {
+            tracer.set_summary(true);
+            continue;
+        } else if head == "-C" {
+            tracer.set_summary(false);
+            continue;
+        } else if head == "--merge" {
+            tracer.set_merge();
+            continue;
+        } else if head == "--json" {
+            tracer.set_json();
+            continue;
+        } else if head == "--no-follow" {
+            tracer.set_follow(peek::FOLLOW::NONE);
+            continue;
+        } else if head == "--follow-threads" {
+            tracer.set_follow(peek::FOLLOW::THREAD);
+            continue;
+        } else if head == "--output-separately" {
+            tracer.set_output_separately();
+            continue;
+        } else if head == "--failed-only" {
+            tracer.set_result_filter(printer::RESULT::FAILED);
+            continue;
+        } else if head == "--successful-only" {
+            tracer.set_result_filter(printer::RESULT::SUCCESS);
+            continue;
+        } else if head == "-y" {
+            tracer.set_fd_path();
+            continue;
+        } else if head == "--seccomp-bpf" {
+            tracer.set_seccomp();
+            continue;
         } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "-p", collect_pid_for_attach) {
             continue;
+        } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "--tid", collect_tid_for_attach) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--cgroup", attach_cgroup) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--all-in-pidns", attach_pidns) {
+            continue;
         } else if parse_opt_cb(tracer, &head, &mut args, "-o", set_output) {
             continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--record", set_record) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--replay", set_replay) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--inject", add_inject) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--redirect-path", add_redirect_path) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--dump-io", add_dump_io) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "-u", set_exec_user) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--env", set_exec_env) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--cwd", set_exec_cwd) {
+            continue;
+        } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "--errno", add_errno_filter) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "--follow-exec", add_follow_exec) {
+            continue;
+        } else if parse_opt_cb(tracer, &head, &mut args, "-P", add_path_filter) {
+            continue;
         } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "-e", set_print_not_skip_named_syscall) {
             continue;
         } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "--ee", set_print_not_skip_included_name_syscall) {
@@ -136,7 +342,7 @@ fn parse_opt(tracer: &mut Tracer) {
         } else if parse_opt_comma_separated_cb(tracer, &head, &mut args, "--SS", set_print_nopeek_included_name_syscall) {
             continue;
         }
-        tracer.attach_exec_child(head, args).unwrap();
+        exit_by_error(tracer.attach_exec_child(head, args).map(|_| ()));
         break;
     }
 }
@@ -144,6 +350,6 @@ fn parse_opt(tracer: &mut Tracer) {
 pub fn start() {
     let mut tracer = Tracer::new();
     parse_opt(&mut tracer);
-    tracer.start().unwrap();
+    exit_by_error(tracer.start());
 }
 
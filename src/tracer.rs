@@ -1,6 +1,13 @@
 use std::io::Result;
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use std::os::unix::ffi::OsStrExt;
 
 mod history;
+mod follow;
+mod procs;
+mod record;
 
 #[allow(unused_macros)]
 macro_rules! LINE { () => { println!("{}", line!()) } }
@@ -10,38 +17,159 @@ mod c {
 
     extern "C" {
         pub fn signal(signum: types::SInt, sighander: types::SigHandler) -> SigHandler;
-        pub fn _exit(status: types::SInt);
+        pub fn siginterrupt(sig: types::SInt, flag: types::SInt) -> types::SInt;
+        pub fn access(pathname: *const std::ffi::c_char, mode: types::SInt) -> types::SInt;
     }
     pub const SIGINT: types::SInt = 2;
+    pub const W_OK: types::SInt = 2;
 }
 
 fn signal(signum: types::SInt, sighandler: types::SigHandler) {
     unsafe { c::signal(signum, sighandler); }
 }
 
-extern fn sighandle_exit (_: types::SInt) {
-    unsafe { c::_exit(1); }
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn sighandle_interrupt (_: types::SInt) {
+    INTERRUPTED.store(true, Ordering::Relaxed);
 }
 
-fn event_loop(printer: printer::Printer) -> Result<()> {
+/// Trace until all tracees exit or interrupted
+/// # Arguments
+/// * `tracees` - Traced thread IDs, updated by fork and exit
+/// * `rec` - Recorder of events output by printer
+fn event_loop(printer: &printer::Printer, follow: &mut follow::FollowExec, tracees: &mut BTreeSet<types::Pid>, rec: &mut record::Recorder) -> Result<()> {
     let mut history = history::HistMap::new();
+    // tracees kept stopped by injected delay until the time, then output and restarted
+    let mut delayed = BTreeSet::<(Instant, types::Pid)>::new();
     let log = printer;
     loop {
-        match peek::wait_event() {
+        // signal may be received out of waiting
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            // already stopped, so not interrupted at detach
+            for (_, pid) in std::mem::take(&mut delayed) {
+                tracees.remove(&pid);
+                let _ = peek::detach_process(pid);
+            }
+            break;
+        }
+        let now = Instant::now();
+        while let Some((t, pid)) = delayed.first().copied() {
+            if t > now {
+                break;
+            }
+            delayed.remove(&(t, pid));
+            if let Some(e) = history.get(pid) {
+                log.output_and_cont(pid, e)?;
+                rec.syscall(pid, e)?;
+            }
+        }
+        let event = match delayed.first() {
+            None => peek::wait_event(),
+            Some((t, _)) => match peek::wait_event_timeout(t.saturating_duration_since(now)) {
+                Ok(Some(event)) => Ok(event),
+                Ok(None) => continue,
+                Err(e) => Err(e),
+            },
+        };
+        match event {
             Ok((pid, peek::ChildEventKind::ForkStop)) => {
-                peek::treat_stopped_clone_process(pid)?;
+                let child = peek::treat_stopped_clone_process(pid)?;
+                tracees.insert(child);
+                follow.fork(pid, child);
+                log.start_process(child)?;
+                rec.start_process(child)?;
             },
-            Ok((pid, peek::ChildEventKind::ExitDone)) => {
+            Ok((pid, peek::ChildEventKind::ExitDone(status))) => {
+                tracees.remove(&pid);
+                delayed.retain(|(_, p)| *p != pid);
                 history.clear(pid);
+                log.exit_process(pid)?;
+                rec.exit_process(pid)?;
+                if follow.is_selected(pid) {
+                    log.output_exited(pid, status)?;
+                    rec.exited(pid, status)?;
+                }
+                follow.exit(pid);
             },
-            Ok((pid, peek::ChildEventKind::SigExited)) => {
+            Ok((pid, peek::ChildEventKind::SigExited(sig, core))) => {
+                tracees.remove(&pid);
+                delayed.retain(|(_, p)| *p != pid);
                 history.clear(pid);
+                log.exit_process(pid)?;
+                rec.exit_process(pid)?;
+                if follow.is_selected(pid) {
+                    log.output_killed(pid, sig, core)?;
+                    rec.killed(pid, sig, core)?;
+                }
+                follow.exit(pid);
+            },
+            Ok((pid, peek::ChildEventKind::SignalStop(sig, info))) => {
+                let r = if follow.is_selected(pid) { log.output_signal(pid, sig, &info).and_then(|_| rec.signal(pid, sig, &info)) } else { Ok(()) };
+                peek::cont_process_with_signal(pid, sig)?;
+                r?;
+            },
+            Ok((pid, peek::ChildEventKind::ExecStop(former))) => {
+                if former != pid {
+                    // execve by non-leader thread, the thread takes over leader's ID and leader is gone without exit event
+                    tracees.remove(&former);
+                    history.rename(former, pid);
+                    log.exit_process(pid)?;
+                    log.exit_process(former)?;
+                    rec.exit_process(pid)?;
+                    rec.exit_process(former)?;
+                }
+                if !follow.exec(pid, former) {
+                    // kept traced without syscall stops, its descendants may execute matched binary
+                    history.clear(pid);
+                    log.exit_process(pid)?;
+                    rec.exit_process(pid)?;
+                    peek::cont_process_to_event(pid)?;
+                    continue;
+                }
+                let r = if follow.is_selected(pid) { log.output_exec(pid, former).and_then(|_| rec.exec(pid, former)) } else { Ok(()) };
+                peek::cont_process_to_exit(pid)?;
+                r?;
+            },
+            Ok((pid, peek::ChildEventKind::SyscallStop)) if !follow.is_selected(pid) => {
+                // only fork and exec are required to select process
+                let _ = peek::cont_process_to_event(pid);
             },
             Ok((pid, peek::ChildEventKind::SyscallStop)) => {
-                if let Ok(e) = peek::peek_syscall_info(pid) {
-                    if let Some(e) = history.update(pid, e) {
-                        log.output_and_cont(pid, e).unwrap();
-                    }
+                let info = match (peek::peek_syscall_info(pid), log.injected_return(pid)) {
+                    // result of injected syscall is overwritten, then read again to output
+                    (Ok(peek::SyscallInfo::EXIT(_)), Some(value)) => peek::set_return_value(pid, value).and_then(|_| peek::peek_syscall_info(pid)),
+                    (info, _) => info,
+                };
+                let info = match info.map(|e| history.update(pid, e)) {
+                    Ok(Some(e)) => {
+                        let args = log.redirect_path(pid, e);
+                        if args.is_empty() {
+                            Ok(Some(e))
+                        } else {
+                            // arguments are replaced, then read again to output
+                            if let Err(err) = peek::replace_str_args(pid, &args) {
+                                eprintln!("failed to redirect path of {}: {}", pid, err);
+                            }
+                            peek::peek_syscall_info(pid).map(|e| history.update(pid, e))
+                        }
+                    },
+                    r => r,
+                };
+                match info {
+                    Ok(Some(e)) => {
+                        if log.inject_entry(pid, e) {
+                            let _ = peek::skip_syscall(pid);
+                        }
+                        match log.injected_delay(pid, e) {
+                            Some(d) => { delayed.insert((Instant::now() + d, pid)); },
+                            None => {
+                                log.output_and_cont(pid, e)?;
+                                rec.syscall(pid, e)?;
+                            },
+                        }
+                    },
+                    _ => { let _ = peek::cont_process(pid); },
                 }
             },
             _ => { break; },
@@ -52,47 +180,206 @@ fn event_loop(printer: printer::Printer) -> Result<()> {
 
 pub struct Tracer {
     out_path: Option<String>,
+    out_separately: bool,
     printer: printer::Printer,
+    seccomp: bool,
+    attached: bool,
+    follow: follow::FollowExec,
+    follow_fork: bool,
+    tracees: BTreeSet<types::Pid>,
+    exec_env: peek::ExecEnv,
+    record: record::Recorder,
+    replay: Option<String>,
 }
 
 impl Tracer {
     pub fn new() -> Self {
-        Tracer{ out_path:None, printer:printer::Printer::new() }
+        Tracer{ out_path:None, out_separately: false, printer:printer::Printer::new(), seccomp: false, attached: false, follow: follow::FollowExec::new(), follow_fork: true, tracees: BTreeSet::new(), exec_env: peek::ExecEnv::new(), record: record::Recorder::new(), replay: None }
+    }
+
+    pub fn set_seccomp(&mut self) {
+        self.seccomp = true;
+    }
+
+    pub fn set_follow(&mut self, follow: peek::FOLLOW) {
+        self.follow_fork = follow == peek::FOLLOW::ALL;
+        peek::set_follow(follow);
     }
-    pub fn set_output(&mut self, path: &str) {
+
+    pub fn add_follow_exec(&mut self, pattern: &str) {
+        self.follow.add_pattern(pattern);
+    }
+
+    fn seccomp_filter(&self) -> peek::SeccompFilter {
+        let traced = || arch::sys_uni::map.iter().filter(|(_, nr)| self.printer.is_traced(*nr));
+        let nr64: Vec<u64> = traced().filter_map(|(_, nr)| arch::sys_uni::a64::from_uni(*nr)).collect();
+        let nr32: Vec<u64> = traced().filter_map(|(_, nr)| arch::sys_uni::a32::from_uni(*nr)).collect();
+        peek::SeccompFilter::new(&nr64, &nr32)
+    }
+
+    /// Set log destination, checked to be writable at once to report error before trace
+    pub fn set_output(&mut self, path: &str) -> Result<()> {
+        // not created here, `<path>` is not written if output separately
+        let p = std::path::Path::new(path);
+        let target = if p.exists() { p } else { p.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(std::path::Path::new(".")) };
+        let target = std::ffi::CString::new(target.as_os_str().as_bytes())?;
+        if unsafe { c::access(target.as_ptr(), c::W_OK) } != 0 {
+            let e = std::io::Error::last_os_error();
+            return Err(std::io::Error::new(e.kind(), format!("{}: {}", path, e)));
+        }
         self.out_path = Some(path.to_owned());
+        Ok(())
+    }
+
+    pub fn set_output_separately(&mut self) {
+        self.out_separately = true;
+    }
+
+    /// Record events to the file in addition to output
+    pub fn set_record(&mut self, path: &str) -> Result<()> {
+        self.record = record::Recorder::create(path, self.printer.clock_start())?;
+        Ok(())
+    }
+
+    /// Output recorded events of the file instead of tracing
+    pub fn set_replay(&mut self, path: &str) {
+        self.replay = Some(path.to_owned());
     }
 
     pub fn start(mut self) -> Result<()> {
-        signal(c::SIGINT, sighandle_exit);
-        if let Some(out) = self.out_path {
-            self.printer.file(out);
+        signal(c::SIGINT, sighandle_interrupt);
+        unsafe { c::siginterrupt(c::SIGINT, 1); }
+        match self.out_path {
+            Some(out) if self.out_separately => self.printer.file_separately(out),
+            Some(out) => {
+                // created even if nothing is output
+                std::fs::File::create(&out).map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", out, e)))?;
+                self.printer.file(out)
+            },
+            None => {},
         }
-        event_loop(self.printer)
+        if let Some(path) = self.replay.take() {
+            if !self.tracees.is_empty() {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "tracee can not be specified with --replay"));
+            }
+            record::replay(&path, &mut self.printer)?;
+            return self.printer.output_summary();
+        }
+        let r = event_loop(&self.printer, &mut self.follow, &mut self.tracees, &mut self.record);
+        if INTERRUPTED.load(Ordering::Relaxed) || r.is_err() {
+            // attached processes shall keep running after trace
+            let tracees: Vec<types::Pid> = self.tracees.iter().copied().collect();
+            peek::interrupt_and_detach(&tracees);
+            tracees.iter().for_each(|pid| {
+                let _ = self.printer.exit_process(*pid);
+                let _ = self.record.exit_process(*pid);
+            });
+        }
+        r?;
+        self.record.flush()?;
+        self.printer.output_summary()?;
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+
+    pub fn set_summary(&mut self, only: bool) {
+        self.printer.set_summary(only)
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: printer::TIMESTAMP) {
+        self.printer.set_timestamp(timestamp)
+    }
+
+    pub fn set_duration(&mut self) {
+        self.printer.set_duration()
+    }
+
+    pub fn set_merge(&mut self) {
+        self.printer.set_merge()
+    }
+
+    pub fn set_fd_path(&mut self) {
+        self.printer.set_fd_path()
+    }
+
+    pub fn set_result_filter(&mut self, result: printer::RESULT) {
+        self.printer.set_result_filter(result)
+    }
+
+    pub fn add_errno_filter(&mut self, name: &str) -> bool {
+        self.printer.add_errno_filter(name)
+    }
+
+    pub fn add_path_filter(&mut self, path: &str) {
+        self.printer.add_path_filter(path)
+    }
+
+    pub fn set_json(&mut self) {
+        self.printer.set_output(printer::OUTPUT::JSON)
     }
 
     pub fn set_skip_for_default(&mut self) {
         self.printer.set_skip_for_default()
     }
 
-    pub fn attach_running_process(&self, pid: types::Pid) -> Result<()> {
-        peek::peek_attach_running_process(pid)?;
+    pub fn attach_running_process(&mut self, pid: types::Pid) -> Result<()> {
+        let tids = peek::peek_attach_running_threads(pid)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("pid {}: {}", pid, e)))?;
+        for tid in tids {
+            self.tracees.insert(tid);
+        }
+        self.attached = true;
+        Ok(())
+    }
+
+    /// Attach processes found by /proc, processes exited or already traced are ignored
+    fn attach_found_processes(&mut self, pids: Vec<types::Pid>, what: String) -> Result<()> {
+        let pids: Vec<types::Pid> = pids.into_iter().filter(|pid| !self.tracees.contains(pid)).collect();
+        let attached = pids.into_iter().filter(|pid| self.attach_running_process(*pid).is_ok()).count();
+        if attached == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("no process to attach: {}", what)));
+        }
         Ok(())
     }
 
-    pub fn set_print_skip_by_name(&mut self, name: &str) {
+    pub fn attach_by_name(&mut self, name: &str) -> Result<()> {
+        let pids = procs::find_by_name(name)?;
+        self.attach_found_processes(pids, format!("@{}", name))
+    }
+
+    pub fn attach_cgroup(&mut self, path: &str) -> Result<()> {
+        let pids = procs::find_in_cgroup(path)?;
+        self.attach_found_processes(pids, format!("cgroup {}", path))
+    }
+
+    pub fn attach_pidns(&mut self, pid: types::Pid) -> Result<()> {
+        let pids = procs::find_in_pidns(pid)?;
+        self.attach_found_processes(pids, format!("pid namespace of {}", pid))
+    }
+
+    pub fn attach_running_thread(&mut self, tid: types::Pid) -> Result<()> {
+        peek::peek_attach_running_process(tid)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("tid {}: {}", tid, e)))?;
+        self.attached = true;
+        self.tracees.insert(tid);
+        Ok(())
+    }
+
+    pub fn set_print_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.printer.set_skip_by_name(name)
     }
 
-    pub fn set_print_not_skip_by_name(&mut self, name: &str) {
+    pub fn set_print_not_skip_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.printer.set_not_skip_by_name(name)
     }
 
-    pub fn set_print_simple_by_name(&mut self, name: &str) {
+    pub fn set_print_simple_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.printer.set_simple_by_name(name)
     }
 
-    pub fn set_print_nopeek_by_name(&mut self, name: &str) {
+    pub fn set_print_nopeek_by_name(&mut self, name: &str) -> std::result::Result<(), std::io::Error> {
         self.printer.set_nopeek_by_name(name)
     }
 
@@ -112,11 +399,40 @@ impl Tracer {
         self.printer.set_nopeek_by_include_name(name)
     }
 
-    pub fn attach_exec_child<T>(&self, cmd: String, args: T) -> Result<types::Pid>
+    pub fn add_inject(&mut self, spec: &str) -> Result<()> {
+        self.printer.add_inject(spec)
+    }
+
+    pub fn add_redirect_path(&mut self, spec: &str) -> Result<()> {
+        self.printer.add_redirect_path(spec)
+    }
+
+    pub fn add_dump_io(&mut self, spec: &str) -> Result<()> {
+        self.printer.add_dump_io(spec)
+    }
+
+    pub fn set_exec_user(&mut self, user: &str) -> Result<()> {
+        self.exec_env.set_user(user)
+    }
+
+    pub fn set_exec_env(&mut self, var: &str) {
+        self.exec_env.set_env(var)
+    }
+
+    pub fn set_exec_cwd(&mut self, dir: &str) {
+        self.exec_env.set_cwd(dir)
+    }
+
+    pub fn attach_exec_child<T>(&mut self, cmd: String, args: T) -> Result<types::Pid>
     where
         T: Iterator<Item = String>
     {
-        let pid = peek::peek_attach_exec_child(cmd, args)?;
+        // seccomp filter can not be installed to running process, so stop on all syscalls if attached
+        // also untraced descendants inheriting filter fail syscalls with ENOSYS, so not installed unless all descendants are traced
+        let all_traced = self.follow_fork && !self.follow.is_enabled();
+        let filter = if self.seccomp && !self.attached && all_traced { Some(self.seccomp_filter()) } else { None };
+        let pid = peek::peek_attach_exec_child(cmd, args, filter.as_ref(), &self.exec_env)?;
+        self.tracees.insert(pid);
         Ok(pid)
     }
 
@@ -0,0 +1,109 @@
+use std::collections::BTreeSet;
+
+pub(super) fn basename(path: &[u8]) -> &[u8] {
+    path.rsplit(|c| *c == b'/').next().unwrap_or(path)
+}
+
+/// Select printed processes by executed binary, processes executed other binary are traced without output
+pub struct FollowExec {
+    patterns: Vec<Vec<u8>>,
+    /// Processes executed matched binary, and its descendants
+    selected: BTreeSet<types::Pid>,
+}
+
+impl FollowExec {
+    pub fn new() -> Self {
+        FollowExec{ patterns: vec![], selected: BTreeSet::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Add pattern of executed binary, matched to full path if contains "/", otherwise matched to base name
+    pub fn add_pattern(&mut self, pattern: &str) {
+        self.patterns.push(pattern.as_bytes().to_vec());
+    }
+
+    /// Check whether output of the process is printed
+    pub fn is_selected(&self, pid: types::Pid) -> bool {
+        !self.is_enabled() || self.selected.contains(&pid)
+    }
+
+    /// Child of selected process is selected
+    pub fn fork(&mut self, parent: types::Pid, child: types::Pid) {
+        if self.is_enabled() && self.selected.contains(&parent) {
+            self.selected.insert(child);
+        }
+    }
+
+    fn is_match(&self, path: &[u8]) -> bool {
+        self.patterns.iter().any(|p| if p.contains(&b'/') { printer::glob(p, path) } else { printer::glob(p, basename(path)) })
+    }
+
+    /// Update selection by executed binary, return false if the process is not printed
+    /// # Arguments
+    /// * `pid` - A process ID executed binary
+    /// * `former` - A thread ID called execve, differ from `pid` if non-leader thread called
+    pub fn exec(&mut self, pid: types::Pid, former: types::Pid) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        self.selected.remove(&former);
+        let exe = peek::get_exe(pid).unwrap_or_default();
+        let argv0 = peek::get_argv0(pid).unwrap_or_default();
+        if self.is_match(&exe) || self.is_match(&argv0) {
+            self.selected.insert(pid);
+            true
+        } else {
+            self.selected.remove(&pid);
+            false
+        }
+    }
+
+    pub fn exit(&mut self, pid: types::Pid) {
+        self.selected.remove(&pid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_name() {
+        assert_eq!(basename(b"/usr/bin/ld"), b"ld");
+        assert_eq!(basename(b"ld"), b"ld");
+        assert_eq!(basename(b"/usr/bin/"), b"");
+    }
+
+    #[test]
+    fn match_path_or_basename() {
+        let mut follow = FollowExec::new();
+        assert!(!follow.is_enabled());
+        assert!(follow.is_selected(1));
+        follow.add_pattern("ld*");
+        follow.add_pattern("/opt/*/cc1");
+        assert!(follow.is_match(b"/usr/bin/ld.gold"));
+        assert!(follow.is_match(b"ld"));
+        assert!(!follow.is_match(b"/usr/bin/gold"));
+        assert!(follow.is_match(b"/opt/gcc/cc1"));
+        assert!(!follow.is_match(b"/usr/lib/cc1"));
+        follow.add_pattern("*a*a*a*a*a*a*a*a*b");
+        assert!(!follow.is_match(&[b'a'; 100]));
+    }
+
+    #[test]
+    fn fork_and_exit() {
+        let mut follow = FollowExec::new();
+        follow.add_pattern("cc1");
+        assert!(!follow.is_selected(1));
+        follow.selected.insert(1);
+        follow.fork(1, 2);
+        follow.fork(3, 4);
+        assert!(follow.is_selected(2));
+        assert!(!follow.is_selected(4));
+        follow.exit(2);
+        assert!(!follow.is_selected(2));
+    }
+}
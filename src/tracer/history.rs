@@ -22,17 +22,34 @@ impl HistMap {
         }
     }
 
-    fn update_exit(&mut self, pid: types::Pid, e: peek::SyscallInfoExit) {
-        if let Some(d) = self.data.get_mut(&pid) {
-            d.add_exit(e);
+    fn update_exit(&mut self, pid: types::Pid, e: peek::SyscallInfoExit) -> bool {
+        match self.data.get_mut(&pid) {
+            Some(d) if d.is_entry() => {
+                d.add_exit(e);
+                true
+            },
+            _ => false,
         }
     }
 
+    /// Return None if syscall-exit without syscall-enter, e.g. not traced syscall-enter
     pub fn update(&mut self, pid: types::Pid, e: peek::SyscallInfo) -> Option<&HistData> {
         match e {
             peek::SyscallInfo::ENTRY(e) => self.update_entry(pid, e),
-            peek::SyscallInfo::EXIT(e) => self.update_exit(pid, e),
+            peek::SyscallInfo::EXIT(e) => if !self.update_exit(pid, e) { return None; },
+        }
+        self.data.get(&pid)
+    }
+
+    /// Move history to other process ID, e.g. non-leader thread took over leader's ID by execve
+    pub fn rename(&mut self, from: types::Pid, to: types::Pid) {
+        match self.data.remove(&from) {
+            Some(d) => { self.data.insert(to, d); },
+            None => { self.data.remove(&to); },
         }
+    }
+
+    pub fn get(&self, pid: types::Pid) -> Option<&HistData> {
         self.data.get(&pid)
     }
 
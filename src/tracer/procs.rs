@@ -0,0 +1,91 @@
+use std::io::Result;
+
+/// List process IDs in /proc except this process
+fn list_processes() -> Result<Vec<types::Pid>> {
+    let me = std::process::id() as types::Pid;
+    let pids = std::fs::read_dir("/proc")?
+        .filter_map(|e| e.ok()?.file_name().to_str()?.parse::<types::Pid>().ok())
+        .filter(|pid| *pid != me)
+        .collect();
+    Ok(pids)
+}
+
+fn is_named(pid: types::Pid, name: &[u8]) -> bool {
+    let comm = std::fs::read(format!("/proc/{}/comm", pid)).unwrap_or_default();
+    if comm.strip_suffix(b"\n").unwrap_or(&comm) == name {
+        return true;
+    }
+    let argv0 = peek::get_argv0(pid).unwrap_or_default();
+    !argv0.is_empty() && (argv0 == name || super::follow::basename(&argv0) == name)
+}
+
+/// Find processes by name, matched to /proc/<pid>/comm, first command line argument or its base name
+/// # Arguments
+/// * `name` - A process name
+pub fn find_by_name(name: &str) -> Result<Vec<types::Pid>> {
+    Ok(list_processes()?.into_iter().filter(|pid| is_named(*pid, name.as_bytes())).collect())
+}
+
+/// Read process IDs in cgroup
+/// # Arguments
+/// * `path` - A cgroup directory or its cgroup.procs file
+pub fn find_in_cgroup(path: &str) -> Result<Vec<types::Pid>> {
+    let path = std::path::Path::new(path);
+    let path = if path.is_dir() { path.join("cgroup.procs") } else { path.to_path_buf() };
+    let me = std::process::id() as types::Pid;
+    let pids = std::fs::read_to_string(&path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?
+        .lines()
+        .filter_map(|l| l.trim().parse::<types::Pid>().ok())
+        .filter(|pid| *pid != me)
+        .collect();
+    Ok(pids)
+}
+
+/// Find processes in same PID namespace as the process
+/// # Arguments
+/// * `pid` - A process ID in the target namespace
+pub fn find_in_pidns(pid: types::Pid) -> Result<Vec<types::Pid>> {
+    let ns = |pid: types::Pid| std::fs::read_link(format!("/proc/{}/ns/pid", pid));
+    let target = ns(pid)?;
+    Ok(list_processes()?.into_iter().filter(|p| ns(*p).is_ok_and(|n| n == target)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_name_and_pidns() {
+        let mut child = std::process::Command::new("sleep").arg("10").spawn().unwrap();
+        let pid = child.id() as types::Pid;
+        let me = std::process::id() as types::Pid;
+        // wait exec of the child
+        while std::fs::read(format!("/proc/{}/comm", pid)).is_ok_and(|c| c != b"sleep\n") {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let by_name = find_by_name("sleep");
+        let in_pidns = find_in_pidns(me);
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(by_name.unwrap().contains(&pid));
+        let in_pidns = in_pidns.unwrap();
+        assert!(in_pidns.contains(&pid));
+        assert!(!in_pidns.contains(&me));
+        assert!(find_by_name("no such process name").unwrap().is_empty());
+    }
+
+    #[test]
+    fn in_cgroup() {
+        let dir = std::env::temp_dir().join(format!("nrtrace-test-cgroup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let me = std::process::id();
+        std::fs::write(dir.join("cgroup.procs"), format!("1\n 42 \nx\n{}\n", me)).unwrap();
+        let by_dir = find_in_cgroup(dir.to_str().unwrap());
+        let by_file = find_in_cgroup(dir.join("cgroup.procs").to_str().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(by_dir.unwrap(), vec![1, 42]);
+        assert_eq!(by_file.unwrap(), vec![1, 42]);
+        assert!(find_in_cgroup(dir.to_str().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+//! Binary trace file of events output by printer, replayed later without tracee
+//!
+//! File is magic and wall clock of trace start, then events of
+//! kind, pid, time, body size, body and data read from tracee for the event.
+//! Numbers are little endian, times are nanoseconds from trace start.
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write, Result, Error, ErrorKind};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const MAGIC: &[u8; 4] = b"NRT1";
+
+/// Event kinds, corresponding to printer outputs
+#[derive(Clone, Copy)]#[allow(clippy::upper_case_acronyms)]
+enum EVENT {
+    START = 1,
+    EXIT = 2,
+    EXITED = 3,
+    KILLED = 4,
+    SIGNAL = 5,
+    EXEC = 6,
+    SYSCALL = 7,
+}
+
+impl EVENT {
+    fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            1 => Some(EVENT::START),
+            2 => Some(EVENT::EXIT),
+            3 => Some(EVENT::EXITED),
+            4 => Some(EVENT::KILLED),
+            5 => Some(EVENT::SIGNAL),
+            6 => Some(EVENT::EXEC),
+            7 => Some(EVENT::SYSCALL),
+            _ => None,
+        }
+    }
+}
+
+/// Flags of syscall event
+const SYSCALL_64: u8 = 1;
+const SYSCALL_EXIT: u8 = 2;
+const SYSCALL_ERROR: u8 = 4;
+
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("broken record file: {}", msg))
+}
+
+/// Writer of events, nothing is written unless created by file path
+pub struct Recorder {
+    out: Option<BufWriter<File>>,
+    start: Instant,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder{ out: None, start: Instant::now() }
+    }
+
+    /// Create record file and start to keep data read from tracee
+    /// # Arguments
+    /// * `path` - A record file path
+    /// * `start` - Wall clock and monotonic clock at trace start, event times are relative to it
+    pub fn create(path: &str, start: (SystemTime, Instant)) -> Result<Self> {
+        let file = File::create(path).map_err(|e| Error::new(e.kind(), format!("{}: {}", path, e)))?;
+        let mut out = BufWriter::new(file);
+        let wall = start.0.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO);
+        out.write_all(MAGIC)?;
+        out.write_all(&(wall.as_nanos() as u64).to_le_bytes())?;
+        peek::start_record();
+        Ok(Recorder{ out: Some(out), start: start.1 })
+    }
+
+    fn since_start(&self, t: Instant) -> u64 {
+        t.saturating_duration_since(self.start).as_nanos() as u64
+    }
+
+    fn write_event(&mut self, kind: EVENT, pid: types::Pid, body: &[u8]) -> Result<()> {
+        let now = self.since_start(Instant::now());
+        let Some(out) = &mut self.out else { return Ok(()) };
+        let blobs = peek::take_recorded();
+        out.write_all(&[kind as u8])?;
+        out.write_all(&pid.to_le_bytes())?;
+        out.write_all(&now.to_le_bytes())?;
+        out.write_all(&(body.len() as u32).to_le_bytes())?;
+        out.write_all(body)?;
+        out.write_all(&(blobs.len() as u32).to_le_bytes())?;
+        for b in blobs {
+            out.write_all(&[b.kind as u8])?;
+            out.write_all(&b.pid.to_le_bytes())?;
+            out.write_all(&b.key.to_le_bytes())?;
+            out.write_all(&(b.data.len() as u32).to_le_bytes())?;
+            out.write_all(&b.data)?;
+        }
+        Ok(())
+    }
+
+    pub fn start_process(&mut self, pid: types::Pid) -> Result<()> {
+        self.write_event(EVENT::START, pid, &[])
+    }
+
+    pub fn exit_process(&mut self, pid: types::Pid) -> Result<()> {
+        self.write_event(EVENT::EXIT, pid, &[])
+    }
+
+    pub fn exited(&mut self, pid: types::Pid, status: types::SInt) -> Result<()> {
+        self.write_event(EVENT::EXITED, pid, &status.to_le_bytes())
+    }
+
+    pub fn killed(&mut self, pid: types::Pid, sig: types::SInt, core: bool) -> Result<()> {
+        self.write_event(EVENT::KILLED, pid, &[&sig.to_le_bytes()[..], &[core as u8]].concat())
+    }
+
+    pub fn signal(&mut self, pid: types::Pid, sig: types::SInt, info: &peek::SignalInfo) -> Result<()> {
+        self.write_event(EVENT::SIGNAL, pid, &[&sig.to_le_bytes()[..], info.as_bytes()].concat())
+    }
+
+    pub fn exec(&mut self, pid: types::Pid, former: types::Pid) -> Result<()> {
+        self.write_event(EVENT::EXEC, pid, &former.to_le_bytes())
+    }
+
+    /// Write syscall at syscall-entry or syscall-exit
+    pub fn syscall(&mut self, pid: types::Pid, e: &peek::SyscallSummery) -> Result<()> {
+        if self.out.is_none() {
+            return Ok(());
+        }
+        let ret = e.raw_return();
+        let mut flags = if e.is_64() { SYSCALL_64 } else { 0 };
+        let value = match ret {
+            None => 0,
+            Some(Ok(v)) => { flags |= SYSCALL_EXIT; v },
+            Some(Err(errno)) => { flags |= SYSCALL_EXIT | SYSCALL_ERROR; errno as i64 },
+        };
+        let mut body = vec![flags];
+        body.extend_from_slice(&e.sysnum().to_le_bytes());
+        e.args().iter().for_each(|a| body.extend_from_slice(&a.to_le_bytes()));
+        body.extend_from_slice(&value.to_le_bytes());
+        body.extend_from_slice(&self.since_start(e.entry_time()).to_le_bytes());
+        body.extend_from_slice(&e.exit_time().map_or(0, |t| self.since_start(t)).to_le_bytes());
+        self.write_event(EVENT::SYSCALL, pid, &body)
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        match &mut self.out {
+            Some(out) => out.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reader of little endian numbers from event body
+struct Body<'a> {
+    buf: &'a [u8],
+}
+
+impl Body<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.buf.len() < N {
+            return Err(invalid("short event"));
+        }
+        let (head, rest) = self.buf.split_at(N);
+        self.buf = rest;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+fn read_exact_or_eof(input: &mut impl Read, buf: &mut [u8]) -> Result<bool> {
+    let mut pos = 0;
+    while pos < buf.len() {
+        match input.read(&mut buf[pos..])? {
+            0 if pos == 0 => return Ok(false),
+            0 => return Err(invalid("unexpected end")),
+            n => pos += n,
+        }
+    }
+    Ok(true)
+}
+
+/// Read `len` bytes, buffer grows as read not to allocate broken length
+fn read_vec(input: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    if input.take(len as u64).read_to_end(&mut buf)? != len {
+        return Err(invalid("unexpected end"));
+    }
+    Ok(buf)
+}
+
+fn read_blobs(input: &mut impl Read) -> Result<Vec<peek::Blob>> {
+    let mut head = [0; 4];
+    input.read_exact(&mut head).map_err(|_| invalid("unexpected end"))?;
+    let mut blobs = vec![];
+    for _ in 0..u32::from_le_bytes(head) {
+        let head = read_vec(input, 1 + 4 + 8 + 4)?;
+        let mut b = Body{ buf: &head };
+        let kind = peek::BLOB::from_u8(b.u8()?).ok_or_else(|| invalid("unknown data kind"))?;
+        let pid = b.i32()?;
+        let key = b.u64()?;
+        let len = u32::from_le_bytes(b.take()?) as usize;
+        blobs.push(peek::Blob{ kind, pid, key, data: read_vec(input, len)? });
+    }
+    Ok(blobs)
+}
+
+/// Output recorded events by printer, memory and /proc of tracee are read from the record
+/// # Arguments
+/// * `path` - A record file path
+/// * `printer` - Printer configured by output options, its trace start is set to the recorded one
+pub fn replay(path: &str, printer: &mut printer::Printer) -> Result<()> {
+    let file = File::open(path).map_err(|e| Error::new(e.kind(), format!("{}: {}", path, e)))?;
+    let mut input = BufReader::new(file);
+    let mut head = [0; 12];
+    if !read_exact_or_eof(&mut input, &mut head).unwrap_or(false) || &head[..4] != MAGIC {
+        return Err(invalid("not a record file"));
+    }
+    let wall = UNIX_EPOCH + Duration::from_nanos(u64::from_le_bytes(head[4..].try_into().unwrap()));
+    let start = Instant::now();
+    let at = |ns: u64| start + Duration::from_nanos(ns);
+    printer.set_clock(wall, start);
+    peek::start_replay();
+    let mut head = [0; 1 + 4 + 8 + 4];
+    while read_exact_or_eof(&mut input, &mut head)? {
+        let mut h = Body{ buf: &head };
+        let kind = EVENT::from_u8(h.u8()?).ok_or_else(|| invalid("unknown event"))?;
+        let pid = h.i32()?;
+        let time = h.u64()?;
+        let len = u32::from_le_bytes(h.take()?) as usize;
+        let body = read_vec(&mut input, len)?;
+        let mut b = Body{ buf: &body };
+        let blobs = read_blobs(&mut input)?;
+        printer.set_event_time(Some(at(time)));
+        match kind {
+            EVENT::START => {
+                peek::add_replayed(pid, false, blobs);
+                printer.start_process(pid)?;
+            },
+            EVENT::EXIT => {
+                peek::add_replayed(pid, false, blobs);
+                printer.exit_process(pid)?;
+            },
+            EVENT::EXITED => {
+                peek::add_replayed(pid, false, blobs);
+                printer.output_exited(pid, b.i32()?)?;
+            },
+            EVENT::KILLED => {
+                peek::add_replayed(pid, false, blobs);
+                printer.output_killed(pid, b.i32()?, b.u8()? != 0)?;
+            },
+            EVENT::SIGNAL => {
+                let sig = b.i32()?;
+                let info = peek::SignalInfo::from_bytes(b.buf).ok_or_else(|| invalid("siginfo size"))?;
+                peek::add_replayed(pid, false, blobs);
+                printer.output_signal(pid, sig, &info)?;
+            },
+            EVENT::EXEC => {
+                peek::add_replayed(pid, false, blobs);
+                printer.output_exec(pid, b.i32()?)?;
+            },
+            EVENT::SYSCALL => {
+                let flags = b.u8()?;
+                let nr = b.u64()?;
+                let mut args = [0; 6];
+                for a in args.iter_mut() {
+                    *a = b.u64()?;
+                }
+                let value = b.u64()? as i64;
+                let entry_time = at(b.u64()?);
+                let exit_time = at(b.u64()?);
+                let (ret, exit_time) = match flags {
+                    f if f & SYSCALL_ERROR != 0 => (Some(Err(value as i32)), Some(exit_time)),
+                    f if f & SYSCALL_EXIT != 0 => (Some(Ok(value)), Some(exit_time)),
+                    _ => (None, None),
+                };
+                let e = peek::SyscallSummery::new_recorded(flags & SYSCALL_64 != 0, nr, args, ret, entry_time, exit_time);
+                // memory read at former syscall is not used, it may be changed
+                peek::add_replayed(pid, e.is_entry(), blobs);
+                printer.output_and_cont(pid, &e)?;
+            },
+        }
+    }
+    printer.set_event_time(None);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir().join(format!("nrtrace-test-{}-{}", name, std::process::id())).to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn syscall_round_trip() {
+        let rec_path = temp_path("record.nrt");
+        let out_path = temp_path("record.out");
+        let me = std::process::id() as types::Pid;
+        let path = std::ffi::CString::new("/nrtrace/recorded/path").unwrap();
+        let args = [-100i64 as u64, path.as_ptr() as u64, 0, 0, 0, 0];
+        let mut rec = Recorder::create(&rec_path, (SystemTime::now(), Instant::now())).unwrap();
+        // read from this process itself, kept as blob of the next event
+        assert_eq!(peek::peek_until_null(me, path.as_ptr() as types::Ptr).unwrap(), path.as_bytes());
+        let nr = arch::sys_uni::a64::from_uni(arch::sys_uni::NR::sys_openat).unwrap();
+        let entry = peek::SyscallSummery::new_recorded(true, nr, args, None, Instant::now(), None);
+        rec.syscall(me, &entry).unwrap();
+        let exit = peek::SyscallSummery::new_recorded(true, nr, args, Some(Err(-2)), Instant::now(), Some(Instant::now()));
+        rec.syscall(me, &exit).unwrap();
+        rec.flush().unwrap();
+        drop(path);
+
+        let mut printer = printer::Printer::new();
+        printer.file(out_path.clone());
+        let r = replay(&rec_path, &mut printer);
+        drop(printer);
+        let out = std::fs::read_to_string(&out_path);
+        std::fs::remove_file(&rec_path).unwrap();
+        let _ = std::fs::remove_file(&out_path);
+        r.unwrap();
+        let out = out.unwrap();
+        assert!(out.contains("openat(AT_FDCWD, \"/nrtrace/recorded/path\""), "{}", out);
+        assert!(out.contains("ENOENT"), "{}", out);
+    }
+
+    #[test]
+    fn truncated() {
+        let path = temp_path("truncated.nrt");
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&[EVENT::EXITED as u8, 1, 0, 0, 0]);
+        std::fs::write(&path, &data).unwrap();
+        let r = replay(&path, &mut printer::Printer::new());
+        std::fs::remove_file(&path).unwrap();
+        let e = r.unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+        assert!(e.to_string().contains("unexpected end"), "{}", e);
+    }
+
+    #[test]
+    fn wrong_magic() {
+        let path = temp_path("magic.nrt");
+        std::fs::write(&path, b"NRT0\0\0\0\0\0\0\0\0").unwrap();
+        let r = replay(&path, &mut printer::Printer::new());
+        std::fs::remove_file(&path).unwrap();
+        let e = r.unwrap_err();
+        assert!(e.to_string().contains("not a record file"), "{}", e);
+    }
+}
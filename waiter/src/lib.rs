@@ -5,6 +5,7 @@ mod c {
     }
     pub const __WALL: types::SInt = 0x40000000;
     pub const WSTOPPED: types::SInt = 0x00000002;
+    pub const WNOHANG: types::SInt = 0x00000001;
 }
 
 /// wapper for wait(pid, &status, __WALL), return status or errno
@@ -41,3 +42,12 @@ pub fn wait_any() -> std::io::Result<(types::Pid, types::SInt)> {
     }
 }
 
+/// wapper for wait(-1, &status, __WALL | WNOHANG), return (0, 0) if no child changed state
+pub fn wait_any_nohang() -> std::io::Result<(types::Pid, types::SInt)> {
+    let mut status: types::SInt = 0;
+    match unsafe { c::waitpid(-1 as types::Pid, &mut status, c::__WALL | c::WNOHANG) } {
+        -1 => Err(std::io::Error::last_os_error()),
+        0 => Ok((0, 0)),
+        r => Ok((r, status)),
+    }
+}